@@ -8,5 +8,34 @@ module.exports = async function (provider) {
   // Configure client to use the provider.
   anchor.setProvider(provider);
 
-  // Add your deploy script here.
+  // Deployments from before roles existed have no Roles account, which every
+  // instruction checking a role requires: create it once, with no role granted,
+  // so the admin authorities keep every right until roles are granted.
+  const program = anchor.workspace.VayooContracts;
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("GLOBAL_STATE_SEED")],
+    program.programId
+  );
+  const [roles] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("ROLES_SEED")],
+    program.programId
+  );
+  const connection = provider.connection;
+  if (
+    (await connection.getAccountInfo(globalState)) === null ||
+    (await connection.getAccountInfo(roles)) !== null
+  ) {
+    return;
+  }
+  await program.methods
+    .initializeRoles()
+    .accounts({
+      authority: provider.wallet.publicKey,
+      globalState,
+      roles,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .rpc();
+  console.log("Roles account created:", roles.toBase58());
 };
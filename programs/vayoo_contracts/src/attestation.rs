@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions};
+
+use crate::constants::PRICE_DECIMALS;
+use crate::errors::ErrorCode;
+use crate::oracle::{OraclePrice, OracleSource};
+use crate::states::AttestationSigners;
+
+/// Price of a contract underlying signed off-chain, see `FeedType::SignedAttestation`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Attestation {
+    pub contract: Pubkey,
+    /// Price with `PRICE_DECIMALS` decimals
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+impl Attestation {
+    /// Signed message: contract key, then price and timestamp little endian
+    pub const MESSAGE_LEN: usize = 32 + 8 + 8;
+
+    pub fn from_message(message: &[u8]) -> Option<Self> {
+        if message.len() != Self::MESSAGE_LEN {
+            return None;
+        }
+        Some(Attestation {
+            contract: Pubkey::new_from_array(message[..32].try_into().unwrap()),
+            price: u64::from_le_bytes(message[32..40].try_into().unwrap()),
+            timestamp: i64::from_le_bytes(message[40..48].try_into().unwrap()),
+        })
+    }
+
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::MESSAGE_LEN);
+        message.extend_from_slice(self.contract.as_ref());
+        message.extend_from_slice(&self.price.to_le_bytes());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+}
+
+/// Attestation signed by a threshold of the authorized signers
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VerifiedAttestation {
+    pub attestation: Attestation,
+    /// Bit `i` is set if `AttestationSigners::signers[i]` signed
+    pub signers_mask: u8,
+}
+
+impl VerifiedAttestation {
+    pub fn oracle_price(&self) -> OraclePrice {
+        OraclePrice {
+            price: self.attestation.price as i128,
+            conf: 0,
+            expo: -(PRICE_DECIMALS as i32),
+            publish_time: self.attestation.timestamp,
+        }
+    }
+}
+
+/// (public key, message) pairs verified by an ed25519 program instruction.
+/// Offsets must point into the instruction itself, so the verified data is the data read here.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+
+    let num_signatures = *data.first().ok_or(ErrorCode::InvalidAttestation)? as usize;
+    let mut signed = Vec::with_capacity(num_signatures);
+    for i in 0..num_signatures {
+        let start = HEADER_LEN + i * OFFSETS_LEN;
+        let offsets = data
+            .get(start..start + OFFSETS_LEN)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        let field = |index: usize| u16::from_le_bytes([offsets[2 * index], offsets[2 * index + 1]]);
+        let (signature_ix, public_key_offset, public_key_ix) = (field(1), field(2), field(3));
+        let (message_offset, message_size, message_ix) = (field(4), field(5), field(6));
+        require!(
+            signature_ix == u16::MAX && public_key_ix == u16::MAX && message_ix == u16::MAX,
+            ErrorCode::InvalidAttestation
+        );
+
+        let public_key_offset = public_key_offset as usize;
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        let message_offset = message_offset as usize;
+        let message = data
+            .get(message_offset..message_offset + message_size as usize)
+            .ok_or(ErrorCode::InvalidAttestation)?;
+        signed.push((
+            Pubkey::new_from_array(public_key.try_into().unwrap()),
+            message.to_vec(),
+        ));
+    }
+    Ok(signed)
+}
+
+/// Attestation of `contract` signed by at least `threshold` distinct authorized signers.
+/// Signatures by other keys or for other contracts are ignored, authorized signers
+/// must all attest the same price and timestamp.
+pub fn check_attestations(
+    signers: &AttestationSigners,
+    contract: &Pubkey,
+    signed: &[(Pubkey, Vec<u8>)],
+) -> Result<VerifiedAttestation> {
+    let mut attested: Option<Attestation> = None;
+    let mut signers_mask: u8 = 0;
+    for (public_key, message) in signed {
+        let index = match signers
+            .signers()
+            .iter()
+            .position(|signer| signer == public_key)
+        {
+            Some(index) => index,
+            None => continue,
+        };
+        let attestation = match Attestation::from_message(message) {
+            Some(attestation) if attestation.contract == *contract => attestation,
+            _ => continue,
+        };
+        match attested {
+            Some(previous) => require!(previous == attestation, ErrorCode::ConflictingAttestations),
+            None => attested = Some(attestation),
+        }
+        signers_mask |= 1 << index;
+    }
+
+    let attestation = attested.ok_or(ErrorCode::AttestationThresholdNotReached)?;
+    require!(
+        signers_mask.count_ones() >= signers.threshold as u32,
+        ErrorCode::AttestationThresholdNotReached
+    );
+    Ok(VerifiedAttestation {
+        attestation,
+        signers_mask,
+    })
+}
+
+/// Attestations of a contract signed in the ed25519 instructions of the transaction
+pub struct AttestationSource<'a, 'info> {
+    signers: AttestationSigners,
+    instructions_sysvar: &'a AccountInfo<'info>,
+    contract: Pubkey,
+}
+
+impl AttestationSource<'_, '_> {
+    /// Attestation verified by the ed25519 instructions of the transaction,
+    /// failing if it is more than `max_staleness` seconds older than `current_timestamp`
+    pub fn verify(
+        &self,
+        current_timestamp: i64,
+        max_staleness: u64,
+    ) -> Result<VerifiedAttestation> {
+        let mut signed = Vec::new();
+        let mut index = 0;
+        while let Ok(instruction) =
+            instructions::load_instruction_at_checked(index, self.instructions_sysvar)
+        {
+            if instruction.program_id == ed25519_program::ID {
+                signed.extend(parse_ed25519_instruction(&instruction.data)?);
+            }
+            index += 1;
+        }
+
+        let verified = check_attestations(&self.signers, &self.contract, &signed)?;
+        let timestamp = verified.attestation.timestamp;
+        require!(
+            timestamp <= current_timestamp && current_timestamp - timestamp <= max_staleness as i64,
+            ErrorCode::StaleFeed
+        );
+        msg!(
+            "Attested price {} at {}, signers mask {:#b}",
+            verified.attestation.price,
+            timestamp,
+            verified.signers_mask
+        );
+        Ok(verified)
+    }
+}
+
+impl OracleSource for AttestationSource<'_, '_> {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        Ok(self
+            .verify(current_timestamp, max_staleness)?
+            .oracle_price())
+    }
+}
+
+/// Loads the attestations of `contract`.
+/// `signers_account` holds the authorized signers, the instructions sysvar is looked up in `sources`.
+pub fn load_attestation<'a, 'info>(
+    signers_account: &AccountInfo,
+    sources: &'a [AccountInfo<'info>],
+    contract: &Pubkey,
+) -> Result<AttestationSource<'a, 'info>> {
+    require_keys_eq!(
+        *signers_account.owner,
+        crate::ID,
+        ErrorCode::InvalidOraclefeed
+    );
+    let data = signers_account.try_borrow_data()?;
+    let signers = AttestationSigners::try_deserialize(&mut &data[..])?;
+
+    let instructions_sysvar = sources
+        .iter()
+        .find(|account| instructions::check_id(account.key))
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    Ok(AttestationSource {
+        signers,
+        instructions_sysvar,
+        contract: *contract,
+    })
+}
+
+/// Reads the attestation of `contract` verified by the ed25519 instructions of the transaction
+pub fn read_attestation(
+    signers_account: &AccountInfo,
+    sources: &[AccountInfo],
+    contract: &Pubkey,
+    current_timestamp: i64,
+    max_staleness: u64,
+) -> Result<VerifiedAttestation> {
+    load_attestation(signers_account, sources, contract)?.verify(current_timestamp, max_staleness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Price;
+
+    // Same layout as solana_sdk::ed25519_instruction::new_ed25519_instruction, for several signatures
+    fn ed25519_instruction_data(signed: &[(Pubkey, Vec<u8>)]) -> Vec<u8> {
+        let mut offsets = Vec::new();
+        let mut payload = Vec::new();
+        let payload_start = 2 + 14 * signed.len();
+        for (public_key, message) in signed {
+            let public_key_offset = payload_start + payload.len();
+            payload.extend_from_slice(public_key.as_ref());
+            let signature_offset = payload_start + payload.len();
+            payload.extend_from_slice(&[0; 64]);
+            let message_offset = payload_start + payload.len();
+            payload.extend_from_slice(message);
+            for value in [
+                signature_offset as u16,
+                u16::MAX,
+                public_key_offset as u16,
+                u16::MAX,
+                message_offset as u16,
+                message.len() as u16,
+                u16::MAX,
+            ] {
+                offsets.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let mut data = vec![signed.len() as u8, 0];
+        data.extend(offsets);
+        data.extend(payload);
+        data
+    }
+
+    fn signer_set(threshold: u8) -> (AttestationSigners, Vec<Pubkey>) {
+        let keys: Vec<Pubkey> = (1..=3u8).map(|i| Pubkey::new_from_array([i; 32])).collect();
+        let mut signers = AttestationSigners {
+            threshold,
+            signers_len: keys.len() as u8,
+            ..Default::default()
+        };
+        signers.signers[..keys.len()].copy_from_slice(&keys);
+        (signers, keys)
+    }
+
+    #[test]
+    fn parses_self_referencing_ed25519_instructions() {
+        let key = Pubkey::new_from_array([5; 32]);
+        let data = ed25519_instruction_data(&[(key, vec![1, 2, 3]), (key, vec![4])]);
+        let signed = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(signed, vec![(key, vec![1, 2, 3]), (key, vec![4])]);
+
+        // Offsets pointing to another instruction are not trusted
+        let mut data = ed25519_instruction_data(&[(key, vec![1])]);
+        data[8..10].copy_from_slice(&0u16.to_le_bytes());
+        assert!(parse_ed25519_instruction(&data).is_err());
+    }
+
+    #[test]
+    fn requires_a_threshold_of_matching_attestations() {
+        let (signers, keys) = signer_set(2);
+        let contract = Pubkey::new_from_array([9; 32]);
+        let attestation = Attestation {
+            contract,
+            price: 4_200_000,
+            timestamp: 1_000,
+        };
+        let outsider = Pubkey::new_from_array([8; 32]);
+
+        let signed = vec![
+            (keys[0], attestation.message()),
+            (outsider, attestation.message()),
+            (keys[2], attestation.message()),
+        ];
+        let verified = check_attestations(&signers, &contract, &signed).unwrap();
+        assert_eq!(verified.attestation, attestation);
+        assert_eq!(verified.signers_mask, 0b101);
+        assert_eq!(verified.oracle_price().normalized().unwrap(), Price::new(4_200_000));
+
+        // The same signer twice counts once
+        let signed = vec![
+            (keys[0], attestation.message()),
+            (keys[0], attestation.message()),
+        ];
+        assert!(check_attestations(&signers, &contract, &signed).is_err());
+
+        // Attestations of another contract are ignored
+        let other = Attestation {
+            contract: outsider,
+            ..attestation
+        };
+        let signed = vec![(keys[0], attestation.message()), (keys[1], other.message())];
+        assert!(check_attestations(&signers, &contract, &signed).is_err());
+
+        let conflicting = Attestation {
+            price: 4_300_000,
+            ..attestation
+        };
+        let signed = vec![
+            (keys[0], attestation.message()),
+            (keys[1], conflicting.message()),
+        ];
+        assert!(check_attestations(&signers, &contract, &signed).is_err());
+    }
+}
@@ -1,2 +1,75 @@
 // seeds
 pub const GLOBAL_STATE_SEED: &[u8] = b"GLOBAL_STATE_SEED";
+pub const SETTLEMENT_SAMPLES_SEED: &[u8] = b"SETTLEMENT_SAMPLES_SEED";
+pub const AGGREGATE_FEED_SEED: &[u8] = b"AGGREGATE_FEED_SEED";
+pub const MANUAL_FEED_SEED: &[u8] = b"MANUAL_FEED_SEED";
+pub const ATTESTATION_SIGNERS_SEED: &[u8] = b"ATTESTATION_SIGNERS_SEED";
+pub const RATIO_FEED_SEED: &[u8] = b"RATIO_FEED_SEED";
+pub const BASKET_FEED_SEED: &[u8] = b"BASKET_FEED_SEED";
+pub const EMERGENCY_STATE_SEED: &[u8] = b"EMERGENCY_STATE_SEED";
+pub const ROLES_SEED: &[u8] = b"ROLES_SEED";
+
+/// Most feeds an aggregate feed takes the median of
+pub const MAX_AGGREGATE_SOURCES: usize = 3;
+/// Most feeds a basket feed sums
+pub const MAX_BASKET_COMPONENTS: usize = 8;
+/// Decimals of basket weights, a weight of `10^BASKET_WEIGHT_DECIMALS` holds one unit of the feed
+pub const BASKET_WEIGHT_DECIMALS: u32 = 6;
+/// Most signers an attestation signer set can hold
+pub const MAX_ATTESTATION_SIGNERS: usize = 8;
+/// Exponent aggregated prices are rescaled to before taking their median, ratio or weighted sum
+pub const AGGREGATE_EXPO: i32 = -12;
+
+// settlement
+/// Time after maturity before the authority can propose a settlement price, in seconds
+pub const SETTLEMENT_GRACE_PERIOD_SECS: u64 = 60 * 60;
+/// Time a proposed settlement price can be overridden by the oracle before it is final, in seconds
+pub const SETTLEMENT_CHALLENGE_WINDOW_SECS: u64 = 24 * 60 * 60;
+/// Dispute window of new contracts, in seconds
+pub const DEFAULT_DISPUTE_WINDOW_SECS: u64 = 60 * 60;
+/// Shortest dispute window a contract can be set to, in seconds. Waived by `dev` builds
+pub const MIN_DISPUTE_WINDOW_SECS: u64 = 15 * 60;
+/// Delay between queuing a risk parameter change on a live contract and applying it, in seconds
+pub const PARAMETER_TIMELOCK_SECS: u64 = 24 * 60 * 60;
+/// Capacity of the settlement samples ring buffer
+pub const MAX_SETTLEMENT_SAMPLES: usize = 32;
+
+// oracles
+/// Pyth oracle program on mainnet-beta, where the `dev` and `prod` builds are deployed
+pub const PYTH_MAINNET_PROGRAM_ID: &str = "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH";
+/// Pyth oracle program on devnet
+pub const PYTH_DEVNET_PROGRAM_ID: &str = "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s";
+
+#[cfg(all(feature = "devnet", feature = "localnet"))]
+compile_error!("the devnet and localnet features select different Pyth programs");
+
+/// Program Pyth price accounts must be owned by
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const PYTH_PROGRAM_ID: &str = PYTH_MAINNET_PROGRAM_ID;
+/// Program Pyth price accounts must be owned by
+#[cfg(feature = "devnet")]
+pub const PYTH_PROGRAM_ID: &str = PYTH_DEVNET_PROGRAM_ID;
+/// Program Pyth price accounts must be owned by. Local validators clone mainnet feeds
+/// with their owner, mock feeds are owned by the program given as `PYTH_PROGRAM_ID` at build time
+#[cfg(feature = "localnet")]
+pub const PYTH_PROGRAM_ID: &str = match option_env!("PYTH_PROGRAM_ID") {
+    Some(mock_program_id) => mock_program_id,
+    None => PYTH_MAINNET_PROGRAM_ID,
+};
+
+/// Pyth receiver program owning pull oracle `PriceUpdateV2` accounts, on every cluster
+pub const PYTH_RECEIVER_PROGRAM_ID: &str = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ";
+
+/// Switchboard On-Demand program owning pull feed accounts
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: &str = "SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv";
+
+// prices
+/// Denominator of every ratio expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Staleness window of contracts created before it was configurable, in seconds
+pub const DEFAULT_MAX_STALENESS_SECS: u64 = 60;
+
+/// Decimals every oracle price is normalized to when a contract is created,
+/// whatever the exponent of the feed. Matches the collateral (USDC) decimals.
+pub const PRICE_DECIMALS: u8 = 6;
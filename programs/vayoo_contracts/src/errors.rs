@@ -45,21 +45,71 @@ pub enum ErrorCode {
     #[msg("Contract not in settling mode")]
     NotSettling, // 0x1784
     #[msg("Contract is in settling mode")]
-    IsSettling, // 0x1784
+    IsSettling, // 0x1785
     #[msg("Error in internal accounting")]
-    ErrorAccounting, // 0x1785
+    ErrorAccounting, // 0x1786
     #[msg("LeakInFreeAccountUser")]
-    LeakInFAccount, // 0x1786
+    LeakInFAccount, // 0x1787
     #[msg("Invalid Feed Type")]
-    InvalidFeedType, // 0x1787
+    InvalidFeedType, // 0x1788
     #[msg("Not a valid Switchboard account")]
-    InvalidSwitchboardAccount, // 0x1788
-    #[msg("Switchboard feed has not been updated in 5 minutes")]
-    StaleFeed, // 0x1789
-    #[msg("Switchboard feed exceeded provided confidence interval")]
-    ConfidenceIntervalExceeded, // 0x178a,
+    InvalidSwitchboardAccount, // 0x1789
+    #[msg("Oracle feed has not been updated within the contract staleness window")]
+    StaleFeed, // 0x178a
+    #[msg("Oracle confidence interval is wider than the contract allows")]
+    ConfidenceIntervalExceeded, // 0x178b,
     #[msg("Invalid Feed")]
-    InvalidOraclefeed, // 0x178b
+    InvalidOraclefeed, // 0x178c
     #[msg("Cant be used in prod mode")]
-    NoTestInProd, // 0x178b
+    NoTestInProd, // 0x178d
+    #[msg("Invalid Settlement Mode")]
+    InvalidSettlementMode, // 0x178e
+    #[msg("Settlement window is not open")]
+    SettlementWindowNotOpen, // 0x178f
+    #[msg("Oracle price already recorded as a settlement sample")]
+    SampleAlreadyRecorded, // 0x1790
+    #[msg("No settlement sample recorded in the settlement window")]
+    NoSettlementSamples, // 0x1791
+    #[msg("Settlement sample too close to the previous one")]
+    SampleTooSoon, // 0x1792
+    #[msg("Not enough fresh oracle feeds to reach the aggregate quorum")]
+    OracleQuorumNotReached, // 0x1793
+    #[msg("Settlement grace period not over")]
+    GracePeriodNotOver, // 0x1794
+    #[msg("No settlement price proposed")]
+    NoSettlementProposal, // 0x1795
+    #[msg("Settlement challenge window not over")]
+    ChallengeWindowNotOver, // 0x1796
+    #[msg("Settlement price not final yet, dispute window not over")]
+    SettlementNotFinalized, // 0x1797
+    #[msg("Settlement price is final, dispute window is over")]
+    DisputeWindowOver, // 0x1798
+    #[msg("Pyth price update is not fully verified")]
+    PartiallyVerifiedUpdate, // 0x1799
+    #[msg("Invalid ed25519 attestation instruction")]
+    InvalidAttestation, // 0x179a
+    #[msg("Not enough authorized signers attested the price")]
+    AttestationThresholdNotReached, // 0x179b
+    #[msg("Authorized signers attested different prices")]
+    ConflictingAttestations, // 0x179c
+    #[msg("Protocol is paused")]
+    ProtocolPaused, // 0x179d
+    #[msg("Emergency refund already claimed, nothing left to refund")]
+    EmergencyRefundClaimed, // 0x179e
+    #[msg("Contract is in emergency mode")]
+    EmergencyMode, // 0x179f
+    #[msg("Amplitude cannot change once lcontracts are issued")]
+    AmplitudeLocked, // 0x17a0
+    #[msg("Risk parameters of a live contract only change through the timelock")]
+    ParameterTimelocked, // 0x17a1
+    #[msg("No risk parameter change queued")]
+    NoQueuedParameter, // 0x17a2
+    #[msg("Risk parameter change timelock is not over")]
+    TimelockNotOver, // 0x17a3
+    #[msg("Open interest cap of the contract reached")]
+    OpenInterestCapExceeded, // 0x17a4
+    #[msg("Deposit would exceed the TVL cap of the contract")]
+    ContractTvlCapExceeded, // 0x17a5
+    #[msg("Deposit would exceed the TVL cap of the protocol")]
+    ProtocolTvlCapExceeded, // 0x17a6
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// A halt flag of `contract` was set or cleared
+#[event]
+pub struct HaltUpdated {
+    pub contract: Pubkey,
+    /// `HaltFlag` changed
+    pub halt_flag: u8,
+    pub halted: bool,
+    pub timestamp: i64,
+}
+
+/// The protocol was paused or unpaused
+#[event]
+pub struct PauseUpdated {
+    pub paused: bool,
+    pub pause_allowlist: u64,
+    pub timestamp: i64,
+}
+
+/// The TVL cap of `contract` was set (0: uncapped)
+#[event]
+pub struct ContractTvlCapUpdated {
+    pub contract: Pubkey,
+    pub max_tvl_usdc: u64,
+    pub timestamp: i64,
+}
+
+/// The TVL cap of the protocol was set (0: uncapped)
+#[event]
+pub struct ProtocolTvlCapUpdated {
+    pub max_total_tvl_usdc: u64,
+    pub timestamp: i64,
+}
+
+/// Emergency mode of `contract` was enabled
+#[event]
+pub struct EmergencyModeSet {
+    pub contract: Pubkey,
+    pub started_by: Pubkey,
+    pub escrow_snapshot: u64,
+    pub issued_lcontract_snapshot: u64,
+    pub timestamp: i64,
+}
+
+/// `user` pulled back their collateral from `contract` in emergency mode, and burned their positions
+#[event]
+pub struct EmergencyRefund {
+    pub contract: Pubkey,
+    pub user: Pubkey,
+    pub free_collateral: u64,
+    pub locked_collateral: u64,
+    pub escrow_share: u64,
+    pub lcontract_burned: u64,
+    pub timestamp: i64,
+}
+
+/// A risk parameter change of `contract` was queued, it applies from `effective_at`
+#[event]
+pub struct ParameterQueued {
+    pub contract: Pubkey,
+    /// `RiskParameter` changed
+    pub parameter: u8,
+    pub value: u64,
+    pub effective_at: u64,
+}
+
+/// The queued risk parameter change of `contract` was applied
+#[event]
+pub struct ParameterApplied {
+    pub contract: Pubkey,
+    pub parameter: u8,
+    pub value: u64,
+    pub timestamp: i64,
+}
+
+/// The open interest cap of `contract` changed
+#[event]
+pub struct CapProductChanged {
+    pub contract: Pubkey,
+    pub old: u64,
+    pub new: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,31 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+
+pub fn handle(ctx: Context<AcceptContractAuthority>) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    msg!(
+        "Admin authority transferred from {} to {}",
+        contract_state_m.admin_authority,
+        contract_state_m.pending_admin_authority
+    );
+    contract_state_m.admin_authority = contract_state_m.pending_admin_authority;
+    contract_state_m.pending_admin_authority = Pubkey::default();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptContractAuthority<'info> {
+    pub pending_admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = contract_state.pending_admin_authority == pending_admin_authority.key() @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
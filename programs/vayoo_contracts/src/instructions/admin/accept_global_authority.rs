@@ -0,0 +1,32 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::errors::ErrorCode;
+use crate::states::GlobalState;
+
+pub fn handle(ctx: Context<AcceptGlobalAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    msg!(
+        "Global authority transferred from {} to {}",
+        global_state.authority,
+        global_state.pending_authority
+    );
+    global_state.authority = global_state.pending_authority;
+    global_state.pending_authority = Pubkey::default();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptGlobalAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = global_state.pending_authority == pending_authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
@@ -0,0 +1,36 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminCancelsParameter>) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    msg!(
+        "Queued parameter {} change to {} cancelled",
+        contract_state_m.queued_parameter,
+        contract_state_m.queued_value
+    );
+    contract_state_m.cancel_queued_parameter()
+}
+
+#[derive(Accounts)]
+pub struct AdminCancelsParameter<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -0,0 +1,33 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+
+pub fn handle(
+    ctx: Context<AdminProposesContractAuthority>,
+    new_admin_authority: Pubkey,
+) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    contract_state_m.pending_admin_authority = new_admin_authority;
+    msg!(
+        "Admin authority transfer proposed to {}",
+        new_admin_authority
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminProposesContractAuthority<'info> {
+    // Contract admin
+    pub contract_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = contract_state.admin_authority == contract_authority.key() @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -0,0 +1,27 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::states::GlobalState;
+
+pub fn handle(ctx: Context<AdminProposesGlobalAuthority>, new_authority: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.pending_authority = new_authority;
+    msg!("Global authority transfer proposed to {}", new_authority);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminProposesGlobalAuthority<'info> {
+    // Super User
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
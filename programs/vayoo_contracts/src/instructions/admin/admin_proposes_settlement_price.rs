@@ -0,0 +1,42 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::types::Price;
+
+pub fn handle(ctx: Context<AdminProposesSettlementPrice>, proposed_price: u64) -> Result<()> {
+    let contract_state = &mut ctx.accounts.contract_state;
+    let time_now = Clock::get()?.unix_timestamp as u64;
+
+    require!(!contract_state.is_settling, ErrorCode::AlreadySettling);
+    require!(proposed_price > 0, ErrorCode::InvalidArgument);
+    require!(
+        contract_state.grace_period_over(time_now),
+        ErrorCode::GracePeriodNotOver
+    );
+
+    // A new proposal restarts the challenge window
+    contract_state.proposed_ending_price = Price::new(proposed_price);
+    contract_state.proposed_at = time_now;
+    msg!(
+        "Settlement price {} proposed at {}, final from {}",
+        proposed_price,
+        time_now,
+        contract_state.proposal_challenge_end().unwrap()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminProposesSettlementPrice<'info> {
+    pub contract_authority: Signer<'info>,
+    #[account[
+        mut,
+        seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.as_ref(), contract_state.authority.as_ref()],
+        constraint = contract_state.admin_authority == contract_authority.key() @ ErrorCode::Unauthorized,
+        bump,
+    ]]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
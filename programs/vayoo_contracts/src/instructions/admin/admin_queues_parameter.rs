@@ -0,0 +1,48 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::events::ParameterQueued;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::{RiskParameter, Role};
+
+pub fn handle(ctx: Context<AdminQueuesParameter>, parameter: u8, value: u64) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    let time_now = Clock::get()?.unix_timestamp as u64;
+    let effective_at =
+        contract_state_m.queue_parameter(RiskParameter::try_from(parameter)?, value, time_now)?;
+
+    msg!(
+        "Parameter {} queued to {}, effective at {}",
+        parameter,
+        value,
+        effective_at
+    );
+    emit!(ParameterQueued {
+        contract: contract_state_m.key(),
+        parameter,
+        value,
+        effective_at,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminQueuesParameter<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -2,13 +2,16 @@
 use anchor_lang::prelude::*;
 
 //local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
 use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::{RiskParameter, Role};
 
 pub fn handle(ctx: Context<AdminSetsAmplitude>,input_limiting_amplitude : u64) -> Result<()> {
 
     let contract_state_m = &mut ctx.accounts.contract_state;
-    contract_state_m.limiting_amplitude=input_limiting_amplitude;
-    Ok(())
+    contract_state_m.set_parameter_now(RiskParameter::LimitingAmplitude, input_limiting_amplitude)
 }
 
 #[derive(Accounts)]
@@ -17,9 +20,13 @@ pub struct AdminSetsAmplitude<'info> {
     #[account(mut)]
     pub contract_authority: Signer<'info>,
 
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
     #[account(
         mut,
-        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_authority.key().as_ref()],
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
         bump,
         //has_one = escrow_vault_collateral
     )]
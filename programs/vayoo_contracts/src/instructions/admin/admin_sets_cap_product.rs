@@ -0,0 +1,47 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::events::CapProductChanged;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsCapProduct>, cap_product: u64) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    // A cap under the current open interest only blocks new issuance
+    msg!(
+        "Open interest cap set to {} (issued: {})",
+        cap_product,
+        contract_state_m.global_current_issued_lcontract
+    );
+    let old = contract_state_m.cap_product;
+    contract_state_m.cap_product = cap_product;
+
+    emit!(CapProductChanged {
+        contract: contract_state_m.key(),
+        old,
+        new: cap_product,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsCapProduct<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -0,0 +1,31 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::{RiskParameter, Role};
+
+pub fn handle(ctx: Context<AdminSetsDisputeWindow>, dispute_window_secs: u64) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    contract_state_m.set_parameter_now(RiskParameter::DisputeWindowSecs, dispute_window_secs)
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsDisputeWindow<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -0,0 +1,98 @@
+//libraries
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{EMERGENCY_STATE_SEED, GLOBAL_STATE_SEED, ROLES_SEED};
+use crate::errors::ErrorCode;
+use crate::events::EmergencyModeSet;
+use crate::states::contract_state::ContractState;
+use crate::states::{EmergencyState, GlobalState, Roles};
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsEmergencyMode>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let contract_state = &mut ctx.accounts.contract_state;
+    require!(
+        signer == ctx.accounts.global_state.guardian
+            || ctx
+                .accounts
+                .roles
+                .grants(Role::Pauser, &contract_state.admin_authority, &signer),
+        ErrorCode::Unauthorized
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // For good, users only get their collateral back from now on
+    contract_state.is_halted = true;
+    contract_state.is_halted_deposit = true;
+    contract_state.is_halted_trading = true;
+    contract_state.is_emergency = true;
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+    emergency_state.contract_account = contract_state.key();
+    emergency_state.bump = *ctx.bumps.get("emergency_state").unwrap();
+    emergency_state.started_at = timestamp;
+    emergency_state.started_by = signer;
+    emergency_state.escrow_snapshot = ctx.accounts.escrow_vault_collateral.amount;
+    // Every lcontract in circulation, whoever holds it, claims its share when burned
+    emergency_state.issued_lcontract_snapshot = ctx.accounts.lcontract_mint.supply;
+
+    msg!(
+        "Emergency mode, escrow {} for {} lcontracts",
+        emergency_state.escrow_snapshot,
+        emergency_state.issued_lcontract_snapshot
+    );
+    emit!(EmergencyModeSet {
+        contract: contract_state.key(),
+        started_by: signer,
+        escrow_snapshot: emergency_state.escrow_snapshot,
+        issued_lcontract_snapshot: emergency_state.issued_lcontract_snapshot,
+        timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsEmergencyMode<'info> {
+    /// Contract admin authority, pauser or guardian
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
+        bump,
+        has_one = escrow_vault_collateral
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [EMERGENCY_STATE_SEED, contract_state.key().as_ref()],
+        bump,
+        space = 8 + size_of::<EmergencyState>()
+    )]
+    pub emergency_state: Box<Account<'info, EmergencyState>>,
+
+    pub escrow_vault_collateral: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = contract_state.lcontract_mint)]
+    pub lcontract_mint: Box<Account<'info, Mint>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,26 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::states::GlobalState;
+
+pub fn handle(ctx: Context<AdminSetsGuardian>, guardian: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.guardian = guardian;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsGuardian<'info> {
+    // Super User
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
@@ -0,0 +1,57 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::errors::ErrorCode;
+use crate::events::HaltUpdated;
+use crate::constants::ROLES_SEED;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::{HaltFlag, Role};
+
+pub fn handle(ctx: Context<AdminSetsHalt>, halt_flag: u8, halted: bool) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    let halt = HaltFlag::try_from(halt_flag)?;
+    // Settling mode halts deposits and trading for good
+    require!(
+        halted || halt == HaltFlag::All || !contract_state_m.is_settling,
+        ErrorCode::IsSettling
+    );
+    // Emergency mode is terminal
+    require!(
+        halted || !contract_state_m.is_emergency,
+        ErrorCode::EmergencyMode
+    );
+    match halt {
+        HaltFlag::All => contract_state_m.is_halted = halted,
+        HaltFlag::Deposit => contract_state_m.is_halted_deposit = halted,
+        HaltFlag::Trading => contract_state_m.is_halted_trading = halted,
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    msg!("Halt flag {} set to {} at {}", halt_flag, halted, timestamp);
+    emit!(HaltUpdated {
+        contract: contract_state_m.key(),
+        halt_flag,
+        halted,
+        timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsHalt<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::Pauser, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
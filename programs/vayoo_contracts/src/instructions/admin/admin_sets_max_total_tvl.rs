@@ -0,0 +1,42 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, ROLES_SEED};
+use crate::errors::ErrorCode;
+use crate::events::ProtocolTvlCapUpdated;
+use crate::states::{GlobalState, Roles};
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsMaxTotalTvl>, max_total_tvl_usdc: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    msg!(
+        "Protocol TVL cap set to {} (TVL: {})",
+        max_total_tvl_usdc,
+        global_state.total_tvl_usdc
+    );
+    global_state.max_total_tvl_usdc = max_total_tvl_usdc;
+
+    emit!(ProtocolTvlCapUpdated {
+        max_total_tvl_usdc,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsMaxTotalTvl<'info> {
+    // Super User or parameter admin
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = roles.grants(Role::ParameterAdmin, &global_state.authority, &authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
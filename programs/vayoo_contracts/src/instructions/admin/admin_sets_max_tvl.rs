@@ -0,0 +1,45 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::events::ContractTvlCapUpdated;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsMaxTvl>, max_tvl_usdc: u64) -> Result<()> {
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    // A cap under the current TVL only blocks new deposits
+    msg!(
+        "Contract TVL cap set to {} (TVL: {})",
+        max_tvl_usdc,
+        contract_state_m.current_tvl_usdc
+    );
+    contract_state_m.max_tvl_usdc = max_tvl_usdc;
+
+    emit!(ContractTvlCapUpdated {
+        contract: contract_state_m.key(),
+        max_tvl_usdc,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsMaxTvl<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -0,0 +1,41 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, ROLES_SEED};
+use crate::errors::ErrorCode;
+use crate::events::PauseUpdated;
+use crate::states::{GlobalState, Roles};
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsPaused>, paused: bool, pause_allowlist: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.paused = paused;
+    global_state.pause_allowlist = pause_allowlist;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    msg!("Paused set to {}, allowlist {:#b}", paused, pause_allowlist);
+    emit!(PauseUpdated {
+        paused,
+        pause_allowlist,
+        timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsPaused<'info> {
+    // Super User or pauser
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        constraint = roles.grants(Role::Pauser, &global_state.authority, &authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
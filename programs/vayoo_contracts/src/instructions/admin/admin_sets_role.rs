@@ -0,0 +1,38 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, ROLES_SEED};
+use crate::states::{GlobalState, Roles};
+use crate::types::Role;
+
+pub fn handle(ctx: Context<AdminSetsRole>, role: u8, holder: Pubkey) -> Result<()> {
+    let roles = &mut ctx.accounts.roles;
+    match Role::try_from(role)? {
+        Role::Pauser => roles.pauser = holder,
+        Role::Settler => roles.settler = holder,
+        Role::ParameterAdmin => roles.parameter_admin = holder,
+    }
+    msg!("Role {} granted to {}", role, holder);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsRole<'info> {
+    // Super User
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [ROLES_SEED],
+        bump = roles.bump,
+    )]
+    pub roles: Box<Account<'info, Roles>>,
+}
@@ -0,0 +1,41 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::ROLES_SEED;
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::Roles;
+use crate::types::{RiskParameter, Role};
+
+pub fn handle(
+    ctx: Context<AdminSetsStaleness>,
+    max_staleness_init_secs: u64,
+    max_staleness_settle_secs: u64,
+) -> Result<()> {
+    // Same checks as the timelocked path
+    let contract_state_m = &mut ctx.accounts.contract_state;
+    contract_state_m
+        .set_parameter_now(RiskParameter::MaxStalenessInitSecs, max_staleness_init_secs)?;
+    contract_state_m.set_parameter_now(
+        RiskParameter::MaxStalenessSettleSecs,
+        max_staleness_settle_secs,
+    )
+}
+
+#[derive(Accounts)]
+pub struct AdminSetsStaleness<'info> {
+    // Super User
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
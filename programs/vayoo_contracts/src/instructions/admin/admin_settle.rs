@@ -1,5 +1,3 @@
-use std::cmp::min;
-
 //libraries
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
@@ -7,7 +5,11 @@ use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 use crate::states::UserState;
 //local imports
 use crate::errors::ErrorCode;
+use crate::payoff;
+use crate::types::{Amount, Quantity, Role, Rounding};
+use crate::constants::ROLES_SEED;
 use crate::states::contract_state::ContractState;
+use crate::states::Roles;
 
 pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
@@ -20,29 +22,18 @@ pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
         user_state.authority.as_ref(),
         &[user_state.bump],
     ]];
-    let amplitude = ctx.accounts.contract_state.limiting_amplitude;
-    let local_pyt_multiplier = contract_state.oracle_price_multiplier;
-
-    let adapted_contract_limiting_amplitude = contract_state
-        .limiting_amplitude;
-
-    let midrange = contract_state
-        .limiting_amplitude
-        .checked_div(2)
-        .unwrap();
-
-    let lower_bound = contract_state.starting_price.checked_sub(midrange).unwrap();
-    let upper_bound = contract_state.starting_price + midrange;
-    let mut final_price = contract_state.ending_price;
-    if final_price > upper_bound {
-        final_price = upper_bound;
-    }
-    if final_price < lower_bound {
-        final_price = lower_bound;
-    }
 
-    let mut pnl_lcontract_long = final_price.checked_sub(lower_bound).unwrap();
-    pnl_lcontract_long = min(pnl_lcontract_long, adapted_contract_limiting_amplitude);
+    // Shorts get back what they recorded as locked, not what the band would lock today
+    let user_short_payoff = payoff::compute_locked(
+        contract_state,
+        user_state.scontract_sold_as_user,
+        Amount(user_state.usdc_collateral_locked_as_user),
+    )?;
+    let mm_short_payoff = payoff::compute_locked(
+        contract_state,
+        user_state.lcontract_minted_as_mm,
+        Amount(user_state.usdc_collateral_locked_as_mm),
+    )?;
 
     let mut gains_shorter_mm = 0;
     let mut gains_shorter_user = 0;
@@ -55,29 +46,8 @@ pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
         //0 lcontracts locked on the ata
         //The setteling process for the short is DIFFERENT FROM THE LONG:
 
-        let mut limited_pnl_per_contract_short = upper_bound.checked_sub(final_price).unwrap();
-        limited_pnl_per_contract_short = min(
-            limited_pnl_per_contract_short,
-            adapted_contract_limiting_amplitude,
-        );
-        msg!(&format!(
-            "pnl_scontract  {}",
-            limited_pnl_per_contract_short
-        ));
-
-        let gains_shorter = user_state
-            .scontract_sold_as_user
-            .checked_mul(limited_pnl_per_contract_short)
-            .unwrap()
-            .checked_div(contract_state.oracle_price_multiplier)
-            .unwrap();
-
-        //loss_shorter inited at the amount of collateral locked
-        let mut loss_shorter = user_state
-            .scontract_sold_as_user
-            .checked_mul(contract_state.limiting_amplitude)
-            .unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-        loss_shorter = loss_shorter.checked_sub(gains_shorter).unwrap();
+        let gains_shorter = user_short_payoff.short_payout.0;
+        let loss_shorter = user_short_payoff.escrow_delta.0;
         let cpi_accounts_transfer_from_locked = Transfer {
             from: ctx.accounts.vault_locked_collateral_ata.to_account_info(),
             to: ctx.accounts.vault_free_collateral_ata.to_account_info(),
@@ -126,25 +96,8 @@ pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
     if user_state.lcontract_minted_as_mm > 0 {
         //if false {
 
-        let mut limited_pnl_per_contract_short = upper_bound.checked_sub(final_price).unwrap();
-        limited_pnl_per_contract_short = min(
-            limited_pnl_per_contract_short,
-            adapted_contract_limiting_amplitude,
-        );
-
-        let gains_shorter = user_state
-            .lcontract_minted_as_mm
-            .checked_mul(limited_pnl_per_contract_short)
-            .unwrap()
-            .checked_div(contract_state.oracle_price_multiplier)
-            .unwrap();
-        //loss_shorter inited at the amount of collateral locked
-        let mut loss_shorter = user_state
-            .lcontract_minted_as_mm
-            .checked_mul(contract_state.limiting_amplitude)
-            .unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-
-        loss_shorter = loss_shorter.checked_sub(gains_shorter).unwrap();
+        let gains_shorter = mm_short_payoff.short_payout.0;
+        let loss_shorter = mm_short_payoff.escrow_delta.0;
         let cpi_accounts_transfer_from_locked = Transfer {
             from: ctx.accounts.vault_locked_collateral_ata.to_account_info(),
             to: ctx.accounts.vault_free_collateral_ata.to_account_info(),
@@ -229,7 +182,9 @@ pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
     let vault_final_locked_usdc = ctx.accounts.vault_locked_collateral_ata.to_account_info();
     let vault_final_scontract_value = token::accessor::amount(&vault_final_scontract)?;
     let vault_final_locked_usdc_value = token::accessor::amount(&vault_final_locked_usdc)?;
-    let needed_collateral = vault_final_scontract_value.checked_mul(amplitude).unwrap().checked_div(contract_state_m.oracle_price_multiplier).unwrap();
+    let needed_collateral = contract_state_m
+        .locked_collateral(Quantity(vault_final_scontract_value), Rounding::Down)?
+        .0;
     if needed_collateral > vault_final_locked_usdc_value {
         return err!(ErrorCode::ShortLeaveUnhealthy);
     }
@@ -240,11 +195,8 @@ pub fn handle(ctx: Context<AdminSettle>) -> Result<()> {
 
     //Making sure the whole platform is well collateralized
     let global_final_issued_contract = contract_state_m.global_current_issued_lcontract;
-    let global_needed_collateral = global_final_issued_contract
-        .checked_mul(pnl_lcontract_long)
-        .unwrap()
-        .checked_div(local_pyt_multiplier)
-        .unwrap();
+    let global_needed_collateral =
+        payoff::compute(contract_state_m, global_final_issued_contract)?.long_payout.0;
 
     if global_needed_collateral > contract_state_m.global_current_locked_usdc {
         return err!(ErrorCode::PlatformUnhealthy);
@@ -259,9 +211,13 @@ pub struct AdminSettle<'info> {
     #[account(mut)]
     pub contract_authority: Signer<'info>,
 
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
     #[account(
         mut,
-        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_authority.key().as_ref()],
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::Settler, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
         bump,
         //has_one = escrow_vault_collateral
     )]
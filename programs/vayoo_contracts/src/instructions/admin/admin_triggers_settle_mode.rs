@@ -1,22 +1,19 @@
 //libraries
 use crate::errors::ErrorCode;
+use crate::oracle;
 use anchor_lang::prelude::*;
-use switchboard_v2::AggregatorAccountData;
 
-use crate::states::PriceFeed;
 //local imports
 use crate::states::contract_state::ContractState;
+use crate::types::Price;
 
 pub fn handle(ctx: Context<AdminTriggersSettleMode>,test_settlement_price: u64) -> Result<()> {
     
     let contract_state = &mut ctx.accounts.contract_state;
     require!(contract_state.test_mode==1, ErrorCode::NoTestInProd);
 
-    contract_state.ending_price = test_settlement_price;
-
-    contract_state.is_settling = true;
-    contract_state.is_halted_deposit = true;
-    contract_state.is_halted_trading = true;
+    let time_now = Clock::get()?.unix_timestamp as u64;
+    contract_state.enter_settlement(Price::new(test_settlement_price), time_now);
     Ok(())
 
 }
@@ -26,11 +23,12 @@ pub struct AdminTriggersSettleMode<'info> {
     pub contract_authority: Signer<'info>,
     #[account[
         mut,
-        seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.as_ref(), contract_authority.key().as_ref()],
+        seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.as_ref(), contract_state.authority.as_ref()],
+        constraint = contract_state.admin_authority == contract_authority.key() @ ErrorCode::Unauthorized,
         bump,
-        constraint = pyth_feed.key() == contract_state.oracle_feed_key || switchboard_feed.key() == contract_state.oracle_feed_key
     ]]
     pub contract_state: Box<Account<'info, ContractState>>,
-    pub switchboard_feed: AccountLoader<'info, AggregatorAccountData>,
-    pub pyth_feed: Account<'info, PriceFeed>,
+    /// CHECK: only compared against the contract feed, the price is provided by the caller
+    #[account(constraint = oracle::is_contract_feed(&contract_state, &oracle) @ ErrorCode::InvalidOraclefeed)]
+    pub oracle: UncheckedAccount<'info>,
 }
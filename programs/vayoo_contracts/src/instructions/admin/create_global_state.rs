@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 
 use std::mem::size_of;
 // local
-use crate::{constants::*, states::global_state::GlobalState};
+use crate::{constants::*, states::global_state::GlobalState, types::PausableInstruction};
 
 pub fn handle(ctx: Context<CreateGlobalState>, bump: u8) -> Result<()> {
     msg!("INITIALIZING GLOBAL STATE");
@@ -14,6 +14,8 @@ pub fn handle(ctx: Context<CreateGlobalState>, bump: u8) -> Result<()> {
     global_state.authority = ctx.accounts.authority.key();
     global_state.paused = false;
     global_state.total_tvl_usdc = 0;
+    global_state.guardian = ctx.accounts.authority.key();
+    global_state.pause_allowlist = PausableInstruction::WithdrawCollateral.bit();
 
     Ok(())
 }
@@ -0,0 +1,47 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, MANUAL_FEED_SEED};
+use crate::errors::ErrorCode;
+use crate::states::{GlobalState, ManualFeed};
+
+pub fn handle(ctx: Context<CreateManualFeed>, name: String, expo: i32) -> Result<()> {
+    require!(cfg!(feature = "dev"), ErrorCode::NoTestInProd);
+
+    let manual_feed = &mut ctx.accounts.manual_feed;
+    manual_feed.authority = ctx.accounts.authority.key();
+    manual_feed.bump = *ctx.bumps.get("manual_feed").unwrap();
+    manual_feed.name = name;
+    manual_feed.expo = expo;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateManualFeed<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [MANUAL_FEED_SEED, authority.key().as_ref(), name.as_bytes()],
+        bump,
+        space = 8 + size_of::<ManualFeed>()
+    )]
+    pub manual_feed: Box<Account<'info, ManualFeed>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,78 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{AGGREGATE_FEED_SEED, GLOBAL_STATE_SEED, MAX_AGGREGATE_SOURCES};
+use crate::errors::ErrorCode;
+use crate::states::{AggregateFeed, AggregateSource, GlobalState};
+use crate::types::FeedType;
+
+pub fn handle(
+    ctx: Context<InitializeAggregateFeed>,
+    name: String,
+    sources: Vec<AggregateSource>,
+    quorum: u8,
+) -> Result<()> {
+    require!(
+        !sources.is_empty() && sources.len() <= MAX_AGGREGATE_SOURCES,
+        ErrorCode::InvalidArgument
+    );
+    require!(
+        quorum > 0 && quorum as usize <= sources.len(),
+        ErrorCode::InvalidArgument
+    );
+    for (i, source) in sources.iter().enumerate() {
+        let feed_type = FeedType::try_from(source.feed_type)?;
+        require!(
+            feed_type == FeedType::Pyth
+                || feed_type == FeedType::Switchboard
+                || feed_type == FeedType::SwitchboardOnDemand,
+            ErrorCode::InvalidFeedType
+        );
+        // The same feed twice would count twice towards the quorum
+        require!(
+            sources[..i]
+                .iter()
+                .all(|other| other.feed_key != source.feed_key),
+            ErrorCode::InvalidArgument
+        );
+    }
+
+    let aggregate_feed = &mut ctx.accounts.aggregate_feed;
+    aggregate_feed.authority = ctx.accounts.authority.key();
+    aggregate_feed.bump = *ctx.bumps.get("aggregate_feed").unwrap();
+    aggregate_feed.name = name;
+    aggregate_feed.quorum = quorum;
+    aggregate_feed.sources_len = sources.len() as u8;
+    aggregate_feed.sources[..sources.len()].copy_from_slice(&sources);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeAggregateFeed<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [AGGREGATE_FEED_SEED, authority.key().as_ref(), name.as_bytes()],
+        bump,
+        space = 8 + size_of::<AggregateFeed>()
+    )]
+    pub aggregate_feed: Box<Account<'info, AggregateFeed>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
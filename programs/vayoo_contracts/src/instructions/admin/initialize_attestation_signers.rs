@@ -0,0 +1,65 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{ATTESTATION_SIGNERS_SEED, GLOBAL_STATE_SEED, MAX_ATTESTATION_SIGNERS};
+use crate::errors::ErrorCode;
+use crate::states::{AttestationSigners, GlobalState};
+
+pub fn handle(
+    ctx: Context<InitializeAttestationSigners>,
+    name: String,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_ATTESTATION_SIGNERS,
+        ErrorCode::InvalidArgument
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        ErrorCode::InvalidArgument
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        // The same key twice would count twice towards the threshold
+        require!(!signers[..i].contains(signer), ErrorCode::InvalidArgument);
+    }
+
+    let attestation_signers = &mut ctx.accounts.attestation_signers;
+    attestation_signers.authority = ctx.accounts.authority.key();
+    attestation_signers.bump = *ctx.bumps.get("attestation_signers").unwrap();
+    attestation_signers.name = name;
+    attestation_signers.threshold = threshold;
+    attestation_signers.signers_len = signers.len() as u8;
+    attestation_signers.signers[..signers.len()].copy_from_slice(&signers);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeAttestationSigners<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ATTESTATION_SIGNERS_SEED, authority.key().as_ref(), name.as_bytes()],
+        bump,
+        space = 8 + size_of::<AttestationSigners>()
+    )]
+    pub attestation_signers: Box<Account<'info, AttestationSigners>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
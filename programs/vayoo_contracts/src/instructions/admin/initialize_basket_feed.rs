@@ -0,0 +1,73 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{BASKET_FEED_SEED, GLOBAL_STATE_SEED, MAX_BASKET_COMPONENTS};
+use crate::errors::ErrorCode;
+use crate::states::{BasketComponent, BasketFeed, GlobalState};
+use crate::types::FeedType;
+
+pub fn handle(
+    ctx: Context<InitializeBasketFeed>,
+    name: String,
+    components: Vec<BasketComponent>,
+) -> Result<()> {
+    require!(
+        !components.is_empty() && components.len() <= MAX_BASKET_COMPONENTS,
+        ErrorCode::InvalidArgument
+    );
+    for (i, component) in components.iter().enumerate() {
+        let feed_type = FeedType::try_from(component.feed_type)?;
+        require!(
+            feed_type == FeedType::Pyth
+                || feed_type == FeedType::Switchboard
+                || feed_type == FeedType::SwitchboardOnDemand,
+            ErrorCode::InvalidFeedType
+        );
+        require!(component.weight > 0, ErrorCode::InvalidArgument);
+        // Components are looked up by key, the same feed twice would be ambiguous
+        require!(
+            components[..i]
+                .iter()
+                .all(|other| other.feed_key != component.feed_key),
+            ErrorCode::InvalidArgument
+        );
+    }
+
+    let basket_feed = &mut ctx.accounts.basket_feed;
+    basket_feed.authority = ctx.accounts.authority.key();
+    basket_feed.bump = *ctx.bumps.get("basket_feed").unwrap();
+    basket_feed.name = name;
+    basket_feed.components_len = components.len() as u8;
+    basket_feed.components[..components.len()].copy_from_slice(&components);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeBasketFeed<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [BASKET_FEED_SEED, authority.key().as_ref(), name.as_bytes()],
+        bump,
+        space = 8 + size_of::<BasketFeed>()
+    )]
+    pub basket_feed: Box<Account<'info, BasketFeed>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -2,12 +2,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::mem::size_of;
-use switchboard_v2::{AggregatorAccountData, SWITCHBOARD_PROGRAM_ID};
 
 //local imports
+use crate::constants::{BPS_DENOMINATOR, DEFAULT_DISPUTE_WINDOW_SECS, PRICE_DECIMALS};
 use crate::errors::ErrorCode;
-use crate::states::{contract_state::ContractState, PriceFeed};
-use crate::types::FeedType;
+use crate::oracle;
+use crate::states::contract_state::ContractState;
+use crate::types::{FeedType, OracleConfig, Price};
 
 pub fn handle(
     ctx: Context<InitializeContract>,
@@ -16,6 +17,7 @@ pub fn handle(
     ending_time: u64,
     limiting_amplitude: u64,
     feed_type: u8,
+    oracle_config: OracleConfig,
 ) -> Result<()> {
     msg!("INITIALIZING WEEKLY CONTRACT");
 
@@ -23,6 +25,15 @@ pub fn handle(
         feed_type < FeedType::Unknown as u8,
         ErrorCode::InvalidFeedType
     );
+    require!(
+        oracle_config.max_confidence_bps > 0
+            && oracle_config.max_confidence_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidArgument
+    );
+    require!(
+        oracle_config.max_staleness_init_secs > 0 && oracle_config.max_staleness_settle_secs > 0,
+        ErrorCode::InvalidArgument
+    );
 
     let contract_state = &mut ctx.accounts.contract_state;
     let current_timestamp = Clock::get()?.unix_timestamp;
@@ -30,6 +41,7 @@ pub fn handle(
     // Contract Initialization
     contract_state.name = contract_name;
     contract_state.authority = ctx.accounts.contract_authority.key();
+    contract_state.admin_authority = ctx.accounts.contract_authority.key();
     contract_state.bump = bump;
     contract_state.escrow_vault_collateral = ctx.accounts.escrow_vault_collateral.key();
     contract_state.is_halted = false;
@@ -42,68 +54,37 @@ pub fn handle(
     contract_state.oracle_feed_type = feed_type;
     contract_state.bands_shift = 0;
 
-    if feed_type == FeedType::Pyth as u8 {
-        // PYTH
-        let pyth_feed_price = ctx
-            .accounts
-            .pyth_feed
-            .get_price_no_older_than(current_timestamp, 60)
-            .ok_or(ErrorCode::PythOffline)?;
-
-        let mut multiplicator = (-pyth_feed_price.expo) as u32;
-        let base = 10 as u32;
-        multiplicator = base.pow(multiplicator);
-
-        msg!("Pyth, Initializing at {}", pyth_feed_price.price);
-
-        contract_state.oracle_feed_key = ctx.accounts.pyth_feed.key();
-        contract_state.oracle_price_multiplier = multiplicator as u64;
-        contract_state.starting_price = pyth_feed_price.price as u64;
-        contract_state.vayoo_precisions = multiplicator as u8;
-    } else if feed_type == FeedType::Switchboard as u8 {
-        // SWITCH_BOARD
-        // check feed owner
-        let owner = *ctx.accounts.switchboard_feed.to_account_info().owner;
-        if owner != SWITCHBOARD_PROGRAM_ID {
-            return Err(error!(ErrorCode::InvalidSwitchboardAccount));
-        }
-        let switchboard_feed = &ctx.accounts.switchboard_feed.load()?;
-        let switchboard_result = switchboard_feed.get_result()?;
-        let expo = switchboard_result.scale;
-        let price = switchboard_result.mantissa;
-        msg!("Switchboard raw price, Initializing at {}", price);
-        msg!("Switchboard raw expo, Initializing at {}", expo);
-
-        // check whether the feed has been updated in the last 60 seconds
-        switchboard_feed
-            .check_staleness(Clock::get().unwrap().unix_timestamp, 60)
-            .map_err(|_| error!(ErrorCode::StaleFeed))?;
-
-        let expo_switchboard = (expo) as u32;
-        let base = 10 as u128;
-        let multiplicator_swithchboard = base.pow(expo_switchboard);
-
-        let expo_vayoo = 6 as u32;
-        let base = 10 as u128;
-        let multiplicator_vayoo = base.pow(expo_vayoo);
-        let mut real_price = (price) as u128;
-
-        real_price = real_price
-            .checked_mul(multiplicator_vayoo as u128)
-            .unwrap()
-            .checked_div(multiplicator_swithchboard)
-            .unwrap();
-        msg!("Switchboard, Initializing at {}", real_price);
-
-        contract_state.oracle_feed_key = ctx.accounts.switchboard_feed.key();
-        contract_state.oracle_price_multiplier = multiplicator_vayoo as u64;
-        contract_state.starting_price = real_price as u64;
-        contract_state.vayoo_precisions = expo_vayoo as u8;
-    }
-
-    contract_state.limiting_amplitude = limiting_amplitude;
+    let oracle_price = oracle::read_price(
+        feed_type,
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &contract_state.key(),
+        current_timestamp,
+        oracle_config.max_staleness_init_secs,
+    )?;
+    msg!(
+        "Oracle raw price, Initializing at {}, expo: {}",
+        oracle_price.price,
+        oracle_price.expo
+    );
+    oracle_price.check_confidence(oracle_config.max_confidence_bps)?;
+
+    // Whatever the feed, prices are stored with PRICE_DECIMALS decimals
+    let starting_price = oracle_price.normalized()?;
+    msg!("Initializing at {}", starting_price.raw());
+
+    contract_state.oracle_feed_key = oracle::feed_key(feed_type, &ctx.accounts.oracle)?;
+    contract_state.oracle_price_multiplier = 10u64.pow(PRICE_DECIMALS as u32);
+    contract_state.starting_price = starting_price;
+    contract_state.vayoo_precisions = PRICE_DECIMALS;
+    contract_state.max_confidence_bps = oracle_config.max_confidence_bps;
+    contract_state.max_staleness_init_secs = oracle_config.max_staleness_init_secs;
+    contract_state.max_staleness_settle_secs = oracle_config.max_staleness_settle_secs;
+    contract_state.dispute_window_secs = DEFAULT_DISPUTE_WINDOW_SECS;
+
+    contract_state.limiting_amplitude = Price::new(limiting_amplitude);
     contract_state.starting_time = current_timestamp as u64;
-    contract_state.ending_price = 0;
+    contract_state.ending_price = Price::default();
     contract_state.ending_time = ending_time;
     contract_state.cap_product = 0;
     contract_state.current_tvl_usdc = 0;
@@ -172,8 +153,9 @@ pub struct InitializeContract<'info> {
 
     pub collateral_mint: Box<Account<'info, Mint>>,
 
-    pub switchboard_feed: AccountLoader<'info, AggregatorAccountData>,
-    pub pyth_feed: Account<'info, PriceFeed>,
+    /// CHECK: owner and layout are checked against `feed_type` by the oracle module,
+    /// the sources of an aggregate feed are passed as remaining accounts
+    pub oracle: UncheckedAccount<'info>,
 
     // Programs and Sysvars
     pub system_program: Program<'info, System>,
@@ -0,0 +1,63 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, RATIO_FEED_SEED};
+use crate::errors::ErrorCode;
+use crate::states::{AggregateSource, GlobalState, RatioFeed};
+use crate::types::FeedType;
+
+pub fn handle(
+    ctx: Context<InitializeRatioFeed>,
+    name: String,
+    base: AggregateSource,
+    quote: AggregateSource,
+) -> Result<()> {
+    for leg in [&base, &quote] {
+        let feed_type = FeedType::try_from(leg.feed_type)?;
+        require!(
+            feed_type == FeedType::Pyth
+                || feed_type == FeedType::Switchboard
+                || feed_type == FeedType::SwitchboardOnDemand,
+            ErrorCode::InvalidFeedType
+        );
+    }
+    require_keys_neq!(base.feed_key, quote.feed_key, ErrorCode::InvalidArgument);
+
+    let ratio_feed = &mut ctx.accounts.ratio_feed;
+    ratio_feed.authority = ctx.accounts.authority.key();
+    ratio_feed.bump = *ctx.bumps.get("ratio_feed").unwrap();
+    ratio_feed.name = name;
+    ratio_feed.base = base;
+    ratio_feed.quote = quote;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeRatioFeed<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [RATIO_FEED_SEED, authority.key().as_ref(), name.as_bytes()],
+        bump,
+        space = 8 + size_of::<RatioFeed>()
+    )]
+    pub ratio_feed: Box<Account<'info, RatioFeed>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
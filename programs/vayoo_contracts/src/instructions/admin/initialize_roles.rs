@@ -0,0 +1,41 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, ROLES_SEED};
+use crate::states::{GlobalState, Roles};
+
+pub fn handle(ctx: Context<InitializeRoles>) -> Result<()> {
+    // No role is granted until the authority sets it
+    let roles = &mut ctx.accounts.roles;
+    roles.bump = *ctx.bumps.get("roles").unwrap();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    // Super User
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ROLES_SEED],
+        bump,
+        space = 8 + size_of::<Roles>()
+    )]
+    pub roles: Box<Account<'info, Roles>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
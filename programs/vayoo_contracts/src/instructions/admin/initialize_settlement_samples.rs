@@ -0,0 +1,63 @@
+//libraries
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::{ROLES_SEED, SETTLEMENT_SAMPLES_SEED};
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::{Roles, SettlementSamples};
+use crate::types::{Role, SettlementMode};
+
+pub fn handle(
+    ctx: Context<InitializeSettlementSamples>,
+    settlement_mode: u8,
+    settlement_window_secs: u64,
+) -> Result<()> {
+    require!(
+        SettlementMode::try_from(settlement_mode)? != SettlementMode::Spot,
+        ErrorCode::InvalidSettlementMode
+    );
+    require!(settlement_window_secs > 0, ErrorCode::InvalidArgument);
+
+    let settlement_samples = &mut ctx.accounts.settlement_samples;
+    settlement_samples.contract_state = ctx.accounts.contract_state.key();
+    settlement_samples.bump = *ctx.bumps.get("settlement_samples").unwrap();
+    settlement_samples.count = 0;
+
+    let contract_state = &mut ctx.accounts.contract_state;
+    contract_state.settlement_mode = settlement_mode;
+    contract_state.settlement_window_secs = settlement_window_secs;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeSettlementSamples<'info> {
+    // Super User
+    #[account(mut)]
+    pub contract_authority: Signer<'info>,
+
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::ParameterAdmin, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+
+    #[account(
+        init,
+        payer = contract_authority,
+        seeds = [SETTLEMENT_SAMPLES_SEED, contract_state.key().as_ref()],
+        bump,
+        space = 8 + size_of::<SettlementSamples>()
+    )]
+    pub settlement_samples: Box<Account<'info, SettlementSamples>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,113 @@
+//libraries
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
+
+/// Grows a contract state created before `admin_authority` existed to the current layout,
+/// the seed authority becoming the admin authority
+pub fn handle(ctx: Context<MigrateContractState>) -> Result<()> {
+    let contract_info = ctx.accounts.contract_state.to_account_info();
+    require_keys_eq!(*contract_info.owner, crate::ID, ErrorCode::Invalid);
+
+    // Old layouts start like the current one: discriminator, name, bump, authority,
+    // escrow vault, the four halt and settling flags, collateral and lcontract mints
+    let (name, bump, authority, lcontract_mint) = {
+        let data = contract_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == ContractState::discriminator(),
+            ErrorCode::Invalid
+        );
+        let mut rest = &data[8..];
+        let name = String::deserialize(&mut rest)?;
+        let bump = u8::deserialize(&mut rest)?;
+        let authority = Pubkey::deserialize(&mut rest)?;
+        let _escrow_vault_collateral = Pubkey::deserialize(&mut rest)?;
+        let _flags = <[bool; 4]>::deserialize(&mut rest)?;
+        let _collateral_mint = Pubkey::deserialize(&mut rest)?;
+        let lcontract_mint = Pubkey::deserialize(&mut rest)?;
+        (name, bump, authority, lcontract_mint)
+    };
+    // Only contract PDAs of this program, not any account sharing the discriminator
+    let contract_key = Pubkey::create_program_address(
+        &[
+            name.as_bytes(),
+            lcontract_mint.as_ref(),
+            authority.as_ref(),
+            &[bump],
+        ],
+        ctx.program_id,
+    )
+    .map_err(|_| error!(ErrorCode::Invalid))?;
+    require_keys_eq!(contract_key, contract_info.key(), ErrorCode::Invalid);
+    require_keys_eq!(
+        authority,
+        ctx.accounts.contract_authority.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let new_len = 8 + size_of::<ContractState>();
+    if contract_info.data_len() < new_len {
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(contract_info.lamports());
+        if rent_due > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.contract_authority.to_account_info(),
+                    to: contract_info.clone(),
+                },
+            );
+            system_program::transfer(cpi_ctx, rent_due)?;
+        }
+        contract_info.realloc(new_len, true)?;
+    }
+
+    let mut contract_state = {
+        let data = contract_info.try_borrow_data()?;
+        ContractState::try_deserialize(&mut &data[..])?
+    };
+    if contract_state.admin_authority == Pubkey::default() {
+        contract_state.admin_authority = authority;
+        // Pyth contracts used to store the multiplier itself, truncated, as their decimals
+        if let Some(decimals) = (0..=18u8)
+            .find(|&decimals| 10u64.pow(decimals as u32) == contract_state.oracle_price_multiplier)
+        {
+            contract_state.vayoo_precisions = decimals;
+        }
+        // Deposits were only counted per contract before the protocol TVL was tracked
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_tvl_usdc = global_state
+            .total_tvl_usdc
+            .checked_add(contract_state.current_tvl_usdc)
+            .ok_or(ErrorCode::Overflow)?;
+        let mut data = contract_info.try_borrow_mut_data()?;
+        contract_state.try_serialize(&mut &mut data[..])?;
+    }
+    msg!("Contract state migrated to {} bytes", new_len);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateContractState<'info> {
+    // Seed authority of the contract
+    #[account(mut)]
+    pub contract_authority: Signer<'info>,
+
+    /// CHECK: may not deserialize before migration, owner and discriminator are checked in the handler
+    #[account(mut)]
+    pub contract_state: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [GLOBAL_STATE_SEED], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    // Programs and Sysvars
+    pub system_program: Program<'info, System>,
+}
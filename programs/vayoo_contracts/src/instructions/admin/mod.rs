@@ -2,14 +2,92 @@
 pub mod create_global_state;
 pub use create_global_state::*;
 
+pub mod initialize_aggregate_feed;
+pub use initialize_aggregate_feed::*;
+
+pub mod create_manual_feed;
+pub use create_manual_feed::*;
+
+pub mod set_manual_feed_price;
+pub use set_manual_feed_price::*;
+
+pub mod initialize_ratio_feed;
+pub use initialize_ratio_feed::*;
+
+pub mod initialize_basket_feed;
+pub use initialize_basket_feed::*;
+
+pub mod initialize_attestation_signers;
+pub use initialize_attestation_signers::*;
+
 pub mod initialize_contract;
 pub use initialize_contract::*;
 
 pub mod admin_settle;
 pub use admin_settle::*;
 
+pub mod admin_proposes_settlement_price;
+pub use admin_proposes_settlement_price::*;
+
 pub mod admin_triggers_settle_mode;
 pub use admin_triggers_settle_mode::*;
 
+pub mod admin_queues_parameter;
+pub use admin_queues_parameter::*;
+
+pub mod admin_cancels_parameter;
+pub use admin_cancels_parameter::*;
+
+pub mod admin_sets_cap_product;
+pub use admin_sets_cap_product::*;
+
+pub mod admin_sets_max_tvl;
+pub use admin_sets_max_tvl::*;
+
+pub mod admin_sets_max_total_tvl;
+pub use admin_sets_max_total_tvl::*;
+
 pub mod admin_sets_amplitude;
 pub use admin_sets_amplitude::*;
+
+pub mod initialize_roles;
+pub use initialize_roles::*;
+
+pub mod admin_sets_role;
+pub use admin_sets_role::*;
+
+pub mod admin_proposes_global_authority;
+pub use admin_proposes_global_authority::*;
+
+pub mod accept_global_authority;
+pub use accept_global_authority::*;
+
+pub mod admin_proposes_contract_authority;
+pub use admin_proposes_contract_authority::*;
+
+pub mod accept_contract_authority;
+pub use accept_contract_authority::*;
+
+pub mod migrate_contract_state;
+pub use migrate_contract_state::*;
+
+pub mod admin_sets_emergency_mode;
+pub use admin_sets_emergency_mode::*;
+
+pub mod admin_sets_paused;
+pub use admin_sets_paused::*;
+
+pub mod admin_sets_halt;
+pub use admin_sets_halt::*;
+
+pub mod admin_sets_dispute_window;
+pub use admin_sets_dispute_window::*;
+
+pub mod admin_sets_guardian;
+pub use admin_sets_guardian::*;
+
+pub mod admin_sets_staleness;
+pub use admin_sets_staleness::*;
+
+pub mod initialize_settlement_samples;
+pub use initialize_settlement_samples::*;
@@ -0,0 +1,24 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::errors::ErrorCode;
+use crate::states::ManualFeed;
+
+pub fn handle(ctx: Context<SetManualFeedPrice>, price: i64, conf: u64) -> Result<()> {
+    require!(cfg!(feature = "dev"), ErrorCode::NoTestInProd);
+
+    let manual_feed = &mut ctx.accounts.manual_feed;
+    manual_feed.price = price;
+    manual_feed.conf = conf;
+    manual_feed.publish_time = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetManualFeedPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub manual_feed: Box<Account<'info, ManualFeed>>,
+}
@@ -0,0 +1,31 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::events::ParameterApplied;
+use crate::states::contract_state::ContractState;
+
+pub fn handle(ctx: Context<ApplyQueuedParameter>) -> Result<()> {
+    let contract_state = &mut ctx.accounts.contract_state;
+    let time_now = Clock::get()?.unix_timestamp;
+    let (parameter, value) = contract_state.apply_queued_parameter(time_now as u64)?;
+
+    msg!("Parameter {:?} set to {}", parameter, value);
+    emit!(ParameterApplied {
+        contract: contract_state.key(),
+        parameter: parameter as u8,
+        value,
+        timestamp: time_now,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApplyQueuedParameter<'info> {
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
+        bump,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
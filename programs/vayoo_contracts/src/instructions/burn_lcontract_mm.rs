@@ -4,8 +4,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 
 //local imports
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
 use crate::states::user_state::UserState;
+use crate::types::{Amount, Quantity, Rounding};
 
 pub fn handle(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
     //this function is to allow the market makers to mint the token -> be able to put it in the whirlpool and get liquidity
@@ -19,9 +22,11 @@ pub fn handle(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
     let contract_state_1=&ctx.accounts.contract_state;
     require!(!contract_state_1.is_settling, ErrorCode::IsSettling);
 
-    let amount_to_send = contract_state_1.limiting_amplitude
-        .checked_mul(amount)
-        .unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
+    // Share of what was locked for the minted contracts, the last burn releases the rest
+    let amount_to_send = Amount(ctx.accounts.user_state.usdc_collateral_locked_as_mm).pro_rata(
+        Quantity(amount),
+        Quantity(ctx.accounts.user_state.lcontract_minted_as_mm),
+    )?;
 
     let user_signer_seeds: &[&[&[u8]]] = &[&[
         ctx.accounts.user_state.contract_account.as_ref(),
@@ -43,7 +48,7 @@ pub fn handle(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, user_signer_seeds);
-    token::transfer(cpi_ctx, amount_to_send)?;
+    token::transfer(cpi_ctx, amount_to_send.0)?;
 
     //Burn lcontract
     let cpi_accounts = Burn {
@@ -68,10 +73,12 @@ pub fn handle(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
 
     // Update User State
-    user_state.usdc_collateral_locked_as_mm -= amount_to_send;
+    user_state.usdc_collateral_locked_as_mm = Amount(user_state.usdc_collateral_locked_as_mm)
+        .checked_sub(amount_to_send)?
+        .0;
     user_state.lcontract_minted_as_mm -= amount;
     user_state.contract_position_net += amount as i64;
-    user_state.usdc_free += amount_to_send;
+    user_state.usdc_free = Amount(user_state.usdc_free).checked_add(amount_to_send)?.0;
 
     // Update Contract State
 
@@ -80,22 +87,21 @@ pub fn handle(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
     let vault_final_locked_usdc = ctx.accounts.vault_locked_collateral_ata.to_account_info();
     let vault_final_scontract_value = token::accessor::amount(&vault_final_scontract)?;
     let vault_final_locked_usdc_value = token::accessor::amount(&vault_final_locked_usdc)?;
-    let needed_collateral = vault_final_scontract_value
-        .checked_mul(ctx.accounts.contract_state.limiting_amplitude)
-        .unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
-    if needed_collateral > vault_final_locked_usdc_value {
+    let needed_collateral =
+        contract_state_1.locked_collateral(Quantity(vault_final_scontract_value), Rounding::Down)?;
+    if needed_collateral > Amount(vault_final_locked_usdc_value) {
         return err!(ErrorCode::ShortLeaveUnhealthy);
     }
-    let limit_amplitude_loc = ctx.accounts.contract_state.limiting_amplitude;
     let contract_state = &mut ctx.accounts.contract_state;
-    contract_state.global_current_locked_usdc -= amount_to_send;
+    contract_state.global_current_locked_usdc = Amount(contract_state.global_current_locked_usdc)
+        .checked_sub(amount_to_send)?
+        .0;
     contract_state.global_current_issued_lcontract -= amount;
     //Making sure the whole platform is well collateralized
     let global_final_issued_contract = contract_state.global_current_issued_lcontract;
-    let global_needed_collateral = global_final_issued_contract
-        .checked_mul(limit_amplitude_loc)
-        .unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-    if global_needed_collateral > contract_state.global_current_locked_usdc {
+    let global_needed_collateral =
+        contract_state.locked_collateral(Quantity(global_final_issued_contract), Rounding::Down)?;
+    if global_needed_collateral > Amount(contract_state.global_current_locked_usdc) {
         return err!(ErrorCode::PlatformUnhealthy);
     }
 
@@ -107,6 +113,12 @@ pub struct BurnContractMm<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         token::mint = contract_state.collateral_mint,
@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 use whirlpools::{self, state::*};
 
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::ContractState;
+use crate::states::GlobalState;
+use crate::types::{Amount, Quantity, Rounding};
 use crate::{errors::ErrorCode, states::UserState};
 
 pub fn handle(
@@ -83,10 +86,10 @@ pub fn handle(
     let vault1_after = token::accessor::amount(&vault11)?;
     let vault2_after = token::accessor::amount(&vault22)?;
 
-    let mut delta = 0;
+    let mut delta = Amount(0);
 
     if vault2_after < vault2_before {
-        delta = vault2_before.checked_sub(vault2_after).unwrap();
+        delta = Amount(vault2_before).checked_sub(Amount(vault2_after))?;
     }
 
     let mut amount_bought_back = 0;
@@ -95,13 +98,13 @@ pub fn handle(
     }
 
     //Adapt the amt to lock
-    let mut amount_to_free = ctx
-        .accounts
-        .contract_state
-        .limiting_amplitude
-        .checked_mul(amount_bought_back)
-        .unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
-    amount_to_free = amount_to_free.checked_sub(delta).unwrap();
+    // Share of what was locked for the sold contracts, closing the whole short releases the rest
+    let collateral_to_unlock = Amount(ctx.accounts.user_state.usdc_collateral_locked_as_user)
+        .pro_rata(
+            Quantity(amount_bought_back),
+            Quantity(ctx.accounts.user_state.scontract_sold_as_user),
+        )?;
+    let amount_to_free = collateral_to_unlock.checked_sub(delta)?;
 
     let cpi_accounts_transfer_to_free = Transfer {
         from: ctx.accounts.vault_locked_collateral_ata.to_account_info(),
@@ -111,7 +114,7 @@ pub fn handle(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx =
         CpiContext::new_with_signer(cpi_program, cpi_accounts_transfer_to_free, signer_seeds);
-    token::transfer(cpi_ctx, amount_to_free)?;
+    token::transfer(cpi_ctx, amount_to_free.0)?;
 
     let cpi_accounts = Burn {
         mint: ctx.accounts.lcontract_mint.to_account_info(),
@@ -133,17 +136,18 @@ pub fn handle(
 
     let user_state = &mut ctx.accounts.user_state;
     // Update User State
-    user_state.usdc_free += amount_to_free;
+    user_state.usdc_free = Amount(user_state.usdc_free).checked_add(amount_to_free)?.0;
 
-    user_state.usdc_collateral_locked_as_user -= amount_bought_back
-        .checked_mul(ctx.accounts.contract_state.limiting_amplitude)
-        .unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
+    user_state.usdc_collateral_locked_as_user = Amount(user_state.usdc_collateral_locked_as_user)
+        .checked_sub(collateral_to_unlock)?
+        .0;
     user_state.scontract_sold_as_user -= amount_bought_back;
     user_state.contract_position_net += amount_bought_back as i64;
 
-    let amplitude = ctx.accounts.contract_state.limiting_amplitude;
     let contract_state = &mut ctx.accounts.contract_state;
-    contract_state.global_current_locked_usdc -= amount_bought_back.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
+    contract_state.global_current_locked_usdc = Amount(contract_state.global_current_locked_usdc)
+        .checked_sub(collateral_to_unlock)?
+        .0;
     contract_state.global_current_issued_lcontract -= amount_bought_back;
 
     //Making sure the user vault is well collateralized
@@ -151,15 +155,17 @@ pub fn handle(
     let vault_final_locked_usdc = ctx.accounts.vault_locked_collateral_ata.to_account_info();
     let vault_final_scontract_value = token::accessor::amount(&vault_final_scontract)?;
     let vault_final_locked_usdc_value = token::accessor::amount(&vault_final_locked_usdc)?;
-    let needed_collateral = vault_final_scontract_value.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-    if needed_collateral > vault_final_locked_usdc_value {
+    let needed_collateral =
+        contract_state.locked_collateral(Quantity(vault_final_scontract_value), Rounding::Down)?;
+    if needed_collateral > Amount(vault_final_locked_usdc_value) {
         return err!(ErrorCode::ShortLeaveUnhealthy);
     }
 
     //Making sure the whole platform is well collateralized
     let global_final_issued_contract = contract_state.global_current_issued_lcontract;
-    let global_needed_collateral = global_final_issued_contract.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-    if global_needed_collateral > contract_state.global_current_locked_usdc {
+    let global_needed_collateral =
+        contract_state.locked_collateral(Quantity(global_final_issued_contract), Rounding::Down)?;
+    if global_needed_collateral > Amount(contract_state.global_current_locked_usdc) {
         return err!(ErrorCode::PlatformUnhealthy);
     }
     Ok(())
@@ -169,6 +175,11 @@ pub fn handle(
 pub struct CloseShortUser<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
     #[account[
         mut,
         seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
@@ -4,12 +4,18 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 //local imports
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
 use crate::states::user_state::UserState;
+use crate::states::GlobalState;
 
 pub fn handle(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
     let contract_state = &mut ctx.accounts.contract_state;
+    let global_state = &mut ctx.accounts.global_state;
+
+    contract_state.add_tvl(amount)?;
+    global_state.add_tvl(amount)?;
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.user_collateral_ata.to_account_info(),
@@ -23,7 +29,6 @@ pub fn handle(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
     // Update State
     user_state.usdc_deposited += amount;
     user_state.usdc_free += amount;
-    contract_state.current_tvl_usdc += amount;
 
     Ok(())
 }
@@ -33,6 +38,13 @@ pub struct DepositCollateral<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         associated_token::mint = contract_state.collateral_mint,
@@ -0,0 +1,121 @@
+//libraries
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+//local imports
+use crate::constants::EMERGENCY_STATE_SEED;
+use crate::errors::ErrorCode;
+use crate::events::EmergencyRefund;
+use crate::states::contract_state::ContractState;
+use crate::states::EmergencyState;
+
+pub fn handle(ctx: Context<EmergencyRedeemLcontract>) -> Result<()> {
+    let contract_signer_seeds: &[&[&[u8]]] = &[&[
+        ctx.accounts.contract_state.name.as_bytes(),
+        ctx.accounts.contract_state.lcontract_mint.as_ref(),
+        ctx.accounts.contract_state.authority.as_ref(),
+        &[ctx.accounts.contract_state.bump],
+    ]];
+
+    let lcontract_burned = ctx.accounts.holder_lcontract_ata.amount;
+    require!(lcontract_burned > 0, ErrorCode::EmergencyRefundClaimed);
+    let escrow_share = ctx
+        .accounts
+        .emergency_state
+        .escrow_share(lcontract_burned)
+        .min(ctx.accounts.escrow_vault_collateral.amount);
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.lcontract_mint.to_account_info(),
+        from: ctx.accounts.holder_lcontract_ata.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token::burn(cpi_ctx, lcontract_burned)?;
+
+    if escrow_share > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault_collateral.to_account_info(),
+            to: ctx.accounts.holder_collateral_ata.to_account_info(),
+            authority: ctx.accounts.contract_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, contract_signer_seeds);
+        token::transfer(cpi_ctx, escrow_share)?;
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    msg!(
+        "Emergency redeem: escrow {} for {} lcontracts",
+        escrow_share,
+        lcontract_burned
+    );
+
+    // Update State
+    let contract_state = &mut ctx.accounts.contract_state;
+    contract_state.global_current_issued_lcontract = contract_state
+        .global_current_issued_lcontract
+        .saturating_sub(lcontract_burned);
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+    emergency_state.escrow_refunded += escrow_share;
+    emergency_state.refunds += 1;
+
+    emit!(EmergencyRefund {
+        contract: contract_state.key(),
+        user: ctx.accounts.holder.key(),
+        free_collateral: 0,
+        locked_collateral: 0,
+        escrow_share,
+        lcontract_burned,
+        timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmergencyRedeemLcontract<'info> {
+    /// Any lcontract holder, with or without a user account
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
+        bump,
+        has_one = escrow_vault_collateral
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+
+    #[account(
+        mut,
+        seeds = [EMERGENCY_STATE_SEED, contract_state.key().as_ref()],
+        bump = emergency_state.bump,
+    )]
+    pub emergency_state: Box<Account<'info, EmergencyState>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.lcontract_mint,
+        token::authority = holder
+    )]
+    pub holder_lcontract_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.collateral_mint,
+        token::authority = holder
+    )]
+    pub holder_collateral_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub escrow_vault_collateral: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = contract_state.lcontract_mint)]
+    pub lcontract_mint: Box<Account<'info, Mint>>,
+
+    // Programs and Sysvars
+    pub token_program: Program<'info, Token>,
+}
@@ -1,11 +1,22 @@
 //libraries
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 
 //local imports
+use crate::constants::{EMERGENCY_STATE_SEED, GLOBAL_STATE_SEED};
+use crate::errors::ErrorCode;
+use crate::events::EmergencyRefund;
 use crate::states::contract_state::ContractState;
+use crate::states::{EmergencyState, GlobalState, UserState};
+
 pub fn handle(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    let user_signer_seeds: &[&[&[u8]]] = &[&[
+        ctx.accounts.user_state.contract_account.as_ref(),
+        ctx.accounts.user_state.authority.as_ref(),
+        &[ctx.accounts.user_state.bump],
+    ]];
+
     let contract_signer_seeds: &[&[&[u8]]] = &[&[
         ctx.accounts.contract_state.name.as_bytes(),
         ctx.accounts.contract_state.lcontract_mint.as_ref(),
@@ -13,43 +24,209 @@ pub fn handle(ctx: Context<EmergencyWithdraw>) -> Result<()> {
         &[ctx.accounts.contract_state.bump],
     ]];
 
-    let cpi_accounts_transfer_pnl_long = Transfer {
-        from: ctx.accounts.escrow_vault_collateral.to_account_info(),
-        to: ctx.accounts.user_collateral_wallet_ata.to_account_info(),
-        authority: ctx.accounts.contract_state.to_account_info(),
-    };
-
-    let cpi_program_redeem_pnl_long = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(
-        cpi_program_redeem_pnl_long,
-        cpi_accounts_transfer_pnl_long,
-        contract_signer_seeds,
+    // Whatever the accounting says, the user vaults only hold this user's collateral
+    let free_collateral = ctx.accounts.vault_free_collateral_ata.amount;
+    let locked_collateral = ctx.accounts.vault_locked_collateral_ata.amount;
+    // lcontracts held in a wallet are redeemed by emergency_redeem_lcontract
+    let lcontract_burned = ctx.accounts.vault_lcontract_ata.amount;
+    let scontract_burned = ctx.accounts.vault_locked_scontract_ata.amount;
+    require!(
+        free_collateral + locked_collateral + lcontract_burned + scontract_burned > 0,
+        ErrorCode::EmergencyRefundClaimed
+    );
+    let escrow_share = ctx
+        .accounts
+        .emergency_state
+        .escrow_share(lcontract_burned)
+        .min(ctx.accounts.escrow_vault_collateral.amount);
+
+    for (vault, amount) in [
+        (&ctx.accounts.vault_free_collateral_ata, free_collateral),
+        (&ctx.accounts.vault_locked_collateral_ata, locked_collateral),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = Transfer {
+            from: vault.to_account_info(),
+            to: ctx.accounts.user_collateral_ata.to_account_info(),
+            authority: ctx.accounts.user_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, user_signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    // Positions are worthless from now on, except for the escrow share paid below
+    for (vault, mint, amount) in [
+        (
+            &ctx.accounts.vault_lcontract_ata,
+            &ctx.accounts.lcontract_mint,
+            lcontract_burned,
+        ),
+        (
+            &ctx.accounts.vault_locked_scontract_ata,
+            &ctx.accounts.scontract_mint,
+            scontract_burned,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = Burn {
+            mint: mint.to_account_info(),
+            from: vault.to_account_info(),
+            authority: ctx.accounts.user_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, user_signer_seeds);
+        token::burn(cpi_ctx, amount)?;
+    }
+
+    if escrow_share > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault_collateral.to_account_info(),
+            to: ctx.accounts.user_collateral_ata.to_account_info(),
+            authority: ctx.accounts.contract_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, contract_signer_seeds);
+        token::transfer(cpi_ctx, escrow_share)?;
+    }
+
+    let refunded = free_collateral + locked_collateral + escrow_share;
+    let timestamp = Clock::get()?.unix_timestamp;
+    msg!(
+        "Emergency refund: free {}, locked {}, escrow {} for {} lcontracts",
+        free_collateral,
+        locked_collateral,
+        escrow_share,
+        lcontract_burned
     );
-    token::transfer(cpi_ctx, ctx.accounts.escrow_vault_collateral.amount)?;
 
+    // Update State
+    let user_state = &mut ctx.accounts.user_state;
+    let deposit_withdrawn = user_state.usdc_deposited;
+    user_state.usdc_deposited = 0;
+    user_state.usdc_free = 0;
+    user_state.usdc_collateral_locked_total = 0;
+    user_state.usdc_collateral_locked_as_mm = 0;
+    user_state.usdc_collateral_locked_as_user = 0;
+    user_state.lcontract_bought_as_user = 0;
+    user_state.scontract_sold_as_user = 0;
+    user_state.lcontract_minted_as_mm = 0;
+    user_state.contract_position_net = 0;
+    user_state.usdc_withdrawn = user_state.usdc_withdrawn.saturating_add(refunded);
+    user_state.emergency_refunded_usdc =
+        user_state.emergency_refunded_usdc.saturating_add(refunded);
+    user_state.emergency_refunded_at = timestamp as u64;
+
+    let contract_state = &mut ctx.accounts.contract_state;
+    contract_state.remove_tvl(deposit_withdrawn)?;
+    contract_state.global_current_locked_usdc = contract_state
+        .global_current_locked_usdc
+        .saturating_sub(locked_collateral);
+    contract_state.global_current_issued_lcontract = contract_state
+        .global_current_issued_lcontract
+        .saturating_sub(lcontract_burned);
+    ctx.accounts.global_state.remove_tvl(deposit_withdrawn)?;
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+    emergency_state.escrow_refunded += escrow_share;
+    emergency_state.refunds += 1;
+
+    emit!(EmergencyRefund {
+        contract: contract_state.key(),
+        user: user_state.authority,
+        free_collateral,
+        locked_collateral,
+        escrow_share,
+        lcontract_burned,
+        timestamp,
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct EmergencyWithdraw<'info> {
-    // Super User
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
         bump,
-
+        has_one = escrow_vault_collateral
     )]
     pub contract_state: Box<Account<'info, ContractState>>,
 
-    #[account(mut)]
-    pub user_collateral_wallet_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [EMERGENCY_STATE_SEED, contract_state.key().as_ref()],
+        bump = emergency_state.bump,
+    )]
+    pub emergency_state: Box<Account<'info, EmergencyState>>,
+
+    #[account(
+        mut,
+        seeds = [contract_state.key().as_ref(), user_authority.key().as_ref()],
+        bump,
+        constraint = user_authority.key() == user_state.authority.key() @ ErrorCode::Unauthorized,
+        constraint = user_state.contract_account == contract_state.key() @ErrorCode::Invalid
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        associated_token::mint = contract_state.collateral_mint,
+        associated_token::authority = user_authority
+    )]
+    pub user_collateral_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.collateral_mint,
+        token::authority = user_state
+    )]
+    pub vault_free_collateral_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.collateral_mint,
+        token::authority = user_state
+    )]
+    pub vault_locked_collateral_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.lcontract_mint,
+        token::authority = user_state
+    )]
+    pub vault_lcontract_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = contract_state.scontract_mint,
+        token::authority = user_state
+    )]
+    pub vault_locked_scontract_ata: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
     pub escrow_vault_collateral: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut, address = contract_state.lcontract_mint)]
+    pub lcontract_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, address = contract_state.scontract_mint)]
+    pub scontract_mint: Box<Account<'info, Mint>>,
+
     // Programs and Sysvars
     pub token_program: Program<'info, Token>,
 }
@@ -0,0 +1,31 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::errors::ErrorCode;
+use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
+
+pub fn handle(ctx: Context<FinalizeSettlementProposal>) -> Result<()> {
+    let contract_state = &mut ctx.accounts.contract_state;
+    let time_now = Clock::get()?.unix_timestamp as u64;
+
+    require!(!contract_state.is_settling, ErrorCode::AlreadySettling);
+    let ending_price = contract_state.finalizable_proposal(time_now)?;
+    msg!("Settling at proposed price: {}", ending_price.raw());
+    contract_state.enter_settlement(ending_price, time_now);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSettlementProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub contract_state: Box<Account<'info, ContractState>>,
+}
@@ -4,7 +4,9 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::mem::size_of;
 
 //local imports
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
 use crate::states::user_state::UserState;
 
 pub fn handle(ctx: Context<InitUser>, bump: u8) -> Result<()> {
@@ -34,6 +36,11 @@ pub fn handle(ctx: Context<InitUser>, bump: u8) -> Result<()> {
 pub struct InitUser<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
     #[account(init,
         seeds = [contract_state.key().as_ref(), user_authority.key().as_ref()],
         bump,
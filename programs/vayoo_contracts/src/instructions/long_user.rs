@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 use whirlpools::{self, state::*};
 
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::ContractState;
+use crate::states::GlobalState;
+use crate::types::{Amount, Quantity};
 use crate::{errors::ErrorCode, states::UserState};
 
 pub fn handle(
@@ -72,25 +75,20 @@ pub fn handle(
     // Updating State
     ctx.accounts.vault_lcontract_ata.reload()?;
     let lcontract_bal_after = ctx.accounts.vault_lcontract_ata.amount;
-    let amount_swapped = lcontract_bal_after
-        .checked_sub(lcontract_bal_before)
-        .unwrap();
+    let amount_swapped = Quantity(lcontract_bal_after).checked_sub(Quantity(lcontract_bal_before))?;
 
     ctx.accounts.vault_free_collateral_ata.reload()?;
     let free_usdc_bal_after = ctx.accounts.vault_free_collateral_ata.amount;
-    let usdc_spent = free_usdc_bal_before
-        .checked_sub(free_usdc_bal_after)
-        .unwrap();
+    let usdc_spent = Amount(free_usdc_bal_before).checked_sub(Amount(free_usdc_bal_after))?;
 
-    user_state.usdc_free = user_state.usdc_free.checked_sub(usdc_spent).unwrap();
+    user_state.usdc_free = Amount(user_state.usdc_free).checked_sub(usdc_spent)?.0;
     user_state.contract_position_net = user_state
         .contract_position_net
-        .checked_add(amount_swapped as i64)
-        .unwrap();
-    user_state.lcontract_bought_as_user = user_state
-        .lcontract_bought_as_user
-        .checked_add(amount_swapped)
+        .checked_add(amount_swapped.0 as i64)
         .unwrap();
+    user_state.lcontract_bought_as_user = Quantity(user_state.lcontract_bought_as_user)
+        .checked_add(amount_swapped)?
+        .0;
 
     if user_state.lcontract_bought_as_user != lcontract_bal_after {
         return err!(ErrorCode::ErrorAccounting);
@@ -102,6 +100,11 @@ pub fn handle(
 pub struct LongUser<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
     #[account[
         mut,
         seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
@@ -2,10 +2,12 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::payoff;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
-use std::cmp::min;
 //local imports
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
 pub fn handle(ctx: Context<MmSettleLong>, amount_to_redeem: u64) -> Result<()> {
     let contract_state = &ctx.accounts.contract_state;
 
@@ -19,33 +21,7 @@ pub fn handle(ctx: Context<MmSettleLong>, amount_to_redeem: u64) -> Result<()> {
     ]];
 
     //for this condition, we should also check the amounts of tokens in the token accounts to double check
-
-    let adapted_contract_limiting_amplitude = contract_state
-        .limiting_amplitude;
-
-    let midrange = contract_state
-        .limiting_amplitude
-        .checked_div(2)
-        .unwrap();
-
-    let lower_bound = contract_state.starting_price.checked_sub(midrange).unwrap();
-    let upper_bound = contract_state.starting_price + midrange;
-    let mut final_price = contract_state.ending_price;
-    if final_price > upper_bound {
-        final_price = upper_bound;
-    }
-    if final_price < lower_bound {
-        final_price = lower_bound;
-    }
-
-    let mut pnl_lcontract = final_price.checked_sub(lower_bound).unwrap();
-    pnl_lcontract = min(pnl_lcontract, adapted_contract_limiting_amplitude);
-    msg!(&format!("pnl_lcontract  {}", pnl_lcontract));
-    let gains_longer = amount_to_redeem
-        .checked_mul(pnl_lcontract)
-        .unwrap()
-        .checked_div(contract_state.oracle_price_multiplier)
-        .unwrap();
+    let gains_longer = payoff::compute(contract_state, amount_to_redeem)?.long_payout.0;
 
     let cpi_accounts_transfer_pnl_long = Transfer {
         from: ctx.accounts.escrow_vault_collateral.to_account_info(),
@@ -70,7 +46,6 @@ pub fn handle(ctx: Context<MmSettleLong>, amount_to_redeem: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
     token::burn(cpi_ctx, amount_to_redeem)?;
-    let local_pyt_multiplier = contract_state.oracle_price_multiplier;
     let contract_state_m = &mut ctx.accounts.contract_state;
     contract_state_m.global_current_issued_lcontract = contract_state_m
         .global_current_issued_lcontract
@@ -83,11 +58,8 @@ pub fn handle(ctx: Context<MmSettleLong>, amount_to_redeem: u64) -> Result<()> {
 
     let global_final_issued_contract = contract_state_m.global_current_issued_lcontract;
 
-    let global_needed_collateral = global_final_issued_contract
-        .checked_mul(pnl_lcontract)
-        .unwrap()
-        .checked_div(local_pyt_multiplier)
-        .unwrap();
+    let global_needed_collateral =
+        payoff::compute(contract_state_m, global_final_issued_contract)?.long_payout.0;
 
     if global_needed_collateral > contract_state_m.global_current_locked_usdc {
         return err!(ErrorCode::PlatformUnhealthy);
@@ -101,6 +73,12 @@ pub struct MmSettleLong<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
@@ -28,6 +28,9 @@ pub use mm_settle_long::*;
 pub mod emergency_withdraw;
 pub use emergency_withdraw::*;
 
+pub mod emergency_redeem_lcontract;
+pub use emergency_redeem_lcontract::*;
+
 pub mod user_settle_long;
 pub use user_settle_long::*;
 
@@ -39,3 +42,15 @@ pub use burn_lcontract_mm::*;
 
 pub mod trigger_settle_mode;
 pub use trigger_settle_mode::*;
+
+pub mod finalize_settlement_proposal;
+pub use finalize_settlement_proposal::*;
+
+pub mod resubmit_settlement_price;
+pub use resubmit_settlement_price::*;
+
+pub mod record_settlement_sample;
+pub use record_settlement_sample::*;
+
+pub mod apply_queued_parameter;
+pub use apply_queued_parameter::*;
@@ -0,0 +1,92 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::{GLOBAL_STATE_SEED, SETTLEMENT_SAMPLES_SEED};
+use crate::errors::ErrorCode;
+use crate::oracle;
+use crate::states::contract_state::ContractState;
+use crate::states::{GlobalState, SettlementSample, SettlementSamples};
+use crate::types::SettlementMode;
+
+pub fn handle(ctx: Context<RecordSettlementSample>) -> Result<()> {
+    let contract_state = &ctx.accounts.contract_state;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    require!(
+        SettlementMode::try_from(contract_state.settlement_mode)? != SettlementMode::Spot,
+        ErrorCode::InvalidSettlementMode
+    );
+    require!(!contract_state.is_settling, ErrorCode::AlreadySettling);
+    require!(
+        current_timestamp >= contract_state.settlement_window_start()
+            && current_timestamp <= contract_state.ending_time as i64,
+        ErrorCode::SettlementWindowNotOpen
+    );
+
+    let oracle_price = oracle::read_price(
+        contract_state.oracle_feed_type,
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        &contract_state.key(),
+        current_timestamp,
+        contract_state.settle_staleness(),
+    )?;
+    if contract_state.max_confidence_bps > 0 {
+        oracle_price.check_confidence(contract_state.max_confidence_bps)?;
+    }
+    require!(
+        oracle_price.publish_time >= contract_state.settlement_window_start(),
+        ErrorCode::SettlementWindowNotOpen
+    );
+
+    let settlement_samples = &mut ctx.accounts.settlement_samples;
+    // Each oracle update is recorded once, so the buffer cannot be flooded with a single print
+    if let Some(last) = settlement_samples.last() {
+        require!(
+            oracle_price.publish_time > last.timestamp,
+            ErrorCode::SampleAlreadyRecorded
+        );
+    }
+    // Consecutive updates cannot evict the rest of the window either
+    let min_spacing = SettlementSamples::min_spacing(contract_state.settlement_window_secs);
+    require!(
+        settlement_samples.accepts(oracle_price.publish_time, min_spacing),
+        ErrorCode::SampleTooSoon
+    );
+
+    let sample = SettlementSample {
+        price: oracle_price.to_price(contract_state.vayoo_precisions)?,
+        timestamp: oracle_price.publish_time,
+    };
+    msg!(
+        "Settlement sample: {} at {}",
+        sample.price.raw(),
+        sample.timestamp
+    );
+    settlement_samples.push(sample);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordSettlementSample<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub contract_state: Box<Account<'info, ContractState>>,
+
+    #[account(
+        mut,
+        seeds = [SETTLEMENT_SAMPLES_SEED, contract_state.key().as_ref()],
+        bump = settlement_samples.bump,
+        has_one = contract_state,
+    )]
+    pub settlement_samples: Box<Account<'info, SettlementSamples>>,
+
+    /// CHECK: owner and layout are checked against the contract feed type by the oracle module
+    #[account(constraint = oracle::is_contract_feed(&contract_state, &oracle) @ ErrorCode::InvalidOraclefeed)]
+    pub oracle: UncheckedAccount<'info>,
+}
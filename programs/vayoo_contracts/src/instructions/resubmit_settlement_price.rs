@@ -0,0 +1,66 @@
+//libraries
+use anchor_lang::prelude::*;
+
+//local imports
+use crate::constants::GLOBAL_STATE_SEED;
+use crate::errors::ErrorCode;
+use crate::oracle;
+use crate::settlement;
+use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
+use crate::types::SettlementStage;
+
+pub fn handle(ctx: Context<ResubmitSettlementPrice>) -> Result<()> {
+    let contract_state = &mut ctx.accounts.contract_state;
+    let time_now = Clock::get()?.unix_timestamp;
+
+    match contract_state.settlement_stage(time_now as u64) {
+        SettlementStage::Active => return err!(ErrorCode::NotSettling),
+        SettlementStage::Finalized => return err!(ErrorCode::DisputeWindowOver),
+        SettlementStage::Settling => {}
+    }
+
+    // Settlers only run the cranks, choosing when the price is re-read is an admin right
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == contract_state.admin_authority || signer == ctx.accounts.global_state.guardian,
+        ErrorCode::Unauthorized
+    );
+
+    // Same price source as trigger_settle_mode, sampled contracts recompute from their samples
+    let contract_key = contract_state.key();
+    let ending_price = settlement::ending_price(
+        contract_state,
+        &contract_key,
+        &ctx.accounts.oracle,
+        ctx.remaining_accounts,
+        time_now,
+    )?;
+    msg!(
+        "Ending price re-submitted: {} (was {})",
+        ending_price.raw(),
+        contract_state.ending_price.raw()
+    );
+    // The dispute window keeps running from the first settlement
+    contract_state.ending_price = ending_price;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResubmitSettlementPrice<'info> {
+    /// Contract admin authority or guardian
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub contract_state: Box<Account<'info, ContractState>>,
+
+    /// CHECK: owner and layout are checked against the contract feed type by the oracle module
+    #[account(constraint = oracle::is_contract_feed(&contract_state, &oracle) @ ErrorCode::InvalidOraclefeed)]
+    pub oracle: UncheckedAccount<'info>,
+}
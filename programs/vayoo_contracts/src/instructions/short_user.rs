@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
 use whirlpools::{self, state::*};
 
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::ContractState;
+use crate::states::GlobalState;
+use crate::types::{Amount, Quantity, Rounding};
 use crate::{errors::ErrorCode, states::UserState};
 
 pub fn handle(
@@ -106,17 +109,16 @@ pub fn handle(
     let _vault1_after = token::accessor::amount(&vault11)?;
     let vault2_after = token::accessor::amount(&vault22)?;
 
-    let mut delta = 0;
+    let mut delta = Amount(0);
 
     if vault2_after > vault2_before {
-        delta = vault2_after.checked_sub(vault2_before).unwrap();
+        delta = Amount(vault2_after).checked_sub(Amount(vault2_before))?;
     }
 
-    //Adapt the amt to lock
-    let mut amount_to_send_tolocked = ctx.accounts.contract_state.limiting_amplitude;
-    amount_to_send_tolocked = amount_to_send_tolocked.checked_mul(amount).unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
+    //Adapt the amt to lock, rounded up so the vault covers the worst case payout
+    let collateral_to_lock = contract_state_1.locked_collateral(Quantity(amount), Rounding::Up)?;
 
-    amount_to_send_tolocked = amount_to_send_tolocked.checked_sub(delta).unwrap();
+    let amount_to_send_tolocked = collateral_to_lock.checked_sub(delta)?;
     let cpi_accounts_transfer_to_locked = Transfer {
         from: ctx.accounts.vault_free_collateral_ata.to_account_info(),
         to: ctx.accounts.vault_locked_collateral_ata.to_account_info(),
@@ -125,39 +127,44 @@ pub fn handle(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx =
         CpiContext::new_with_signer(cpi_program, cpi_accounts_transfer_to_locked, signer_seeds);
-    token::transfer(cpi_ctx, amount_to_send_tolocked)?;
-    let amplitude = ctx.accounts.contract_state.limiting_amplitude;
+    token::transfer(cpi_ctx, amount_to_send_tolocked.0)?;
     let user_state = &mut ctx.accounts.user_state;
     // Update User State
-    user_state.usdc_collateral_locked_as_user += amount.checked_mul(amplitude).unwrap().checked_div(contract_state_1.oracle_price_multiplier).unwrap();
+    user_state.usdc_collateral_locked_as_user = Amount(user_state.usdc_collateral_locked_as_user)
+        .checked_add(collateral_to_lock)?
+        .0;
     user_state.scontract_sold_as_user += amount;
     user_state.contract_position_net = user_state
         .contract_position_net
         .checked_sub(amount as i64)
         .unwrap();
-    user_state.usdc_free = user_state
-        .usdc_free
-        .checked_sub(amount_to_send_tolocked)
-        .unwrap();
+    user_state.usdc_free = Amount(user_state.usdc_free)
+        .checked_sub(amount_to_send_tolocked)?
+        .0;
 
     let contract_state = &mut ctx.accounts.contract_state;
-    contract_state.global_current_locked_usdc += amount.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
+    contract_state.global_current_locked_usdc = Amount(contract_state.global_current_locked_usdc)
+        .checked_add(collateral_to_lock)?
+        .0;
     contract_state.global_current_issued_lcontract += amount;
+    contract_state.check_open_interest_cap()?;
 
     //Making sure the user vault is well collateralized
     let vault_final_scontract = ctx.accounts.vault_locked_scontract_ata.to_account_info();
     let vault_final_locked_usdc = ctx.accounts.vault_locked_collateral_ata.to_account_info();
     let vault_final_scontract_value = token::accessor::amount(&vault_final_scontract)?;
     let vault_final_locked_usdc_value = token::accessor::amount(&vault_final_locked_usdc)?;
-    let needed_collateral = vault_final_scontract_value.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-    if needed_collateral > vault_final_locked_usdc_value {
+    let needed_collateral =
+        contract_state.locked_collateral(Quantity(vault_final_scontract_value), Rounding::Down)?;
+    if needed_collateral > Amount(vault_final_locked_usdc_value) {
         return err!(ErrorCode::ShortLeaveUnhealthy);
     }
 
     //Making sure the whole platform is well collateralized
     let global_final_issued_contract = contract_state.global_current_issued_lcontract;
-    let global_needed_collateral = global_final_issued_contract.checked_mul(amplitude).unwrap().checked_div(contract_state.oracle_price_multiplier).unwrap();
-    if global_needed_collateral > contract_state.global_current_locked_usdc {
+    let global_needed_collateral =
+        contract_state.locked_collateral(Quantity(global_final_issued_contract), Rounding::Down)?;
+    if global_needed_collateral > Amount(contract_state.global_current_locked_usdc) {
         return err!(ErrorCode::PlatformUnhealthy);
     }
 
@@ -168,6 +175,11 @@ pub fn handle(
 pub struct ShortUser<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
     #[account[
         mut,
         seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
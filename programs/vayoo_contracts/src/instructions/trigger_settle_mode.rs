@@ -1,12 +1,14 @@
 //libraries
 use crate::errors::ErrorCode;
-use crate::types::FeedType;
+use crate::oracle;
+use crate::settlement;
 use anchor_lang::prelude::*;
-use switchboard_v2::AggregatorAccountData;
 
-use crate::states::PriceFeed;
 //local imports
+use crate::constants::{GLOBAL_STATE_SEED, ROLES_SEED};
 use crate::states::contract_state::ContractState;
+use crate::states::{GlobalState, Roles};
+use crate::types::Role;
 
 pub fn handle(ctx: Context<TriggerSettleMode>) -> Result<()> {
     let contract_state = &mut ctx.accounts.contract_state;
@@ -17,64 +19,17 @@ pub fn handle(ctx: Context<TriggerSettleMode>) -> Result<()> {
     if time_now as u64 > contract_state.ending_time {
         msg!("Settling Mode Triggered");
 
-        let current_timestamp = Clock::get()?.unix_timestamp;
-
-        if contract_state.oracle_feed_type == FeedType::Pyth as u8 {
-            // PYTH
-            require!(
-                contract_state.oracle_feed_key == ctx.accounts.pyth_feed.key(),
-                ErrorCode::InvalidOraclefeed
-            );
-            let pyth_feed_price = ctx
-                .accounts
-                .pyth_feed
-                .get_price_no_older_than(current_timestamp, 60)
-                .ok_or(ErrorCode::PythOffline)?;
-            msg!(
-                "Pyth, Settling at price: {}, expo: {}",
-                pyth_feed_price.price,
-                pyth_feed_price.expo
-            );
-
-            contract_state.ending_price = pyth_feed_price.price as u64;
-        } else if contract_state.oracle_feed_type == FeedType::Switchboard as u8 {
-            // SWITCH_BOARD
-            require!(
-                contract_state.oracle_feed_key == ctx.accounts.switchboard_feed.key(),
-                ErrorCode::InvalidOraclefeed
-            );
-            let switchboard_feed = &ctx.accounts.switchboard_feed.load()?;
-            let switchboard_result = switchboard_feed.get_result()?;
-            let expo = switchboard_result.scale;
-            let price = switchboard_result.mantissa;
-
-            // check whether the feed has been updated in the last 60 seconds
-            switchboard_feed
-                .check_staleness(Clock::get().unwrap().unix_timestamp, 60)
-                .map_err(|_| error!(ErrorCode::StaleFeed))?;
-
-            msg!("Switchboard, Settling at price: {}, expo: {}", price, expo);
-
-
-            let  expo_switchboard = (expo) as u32;
-            let base = 10 as u128;
-            let multiplicator_swithchboard = base.pow(expo_switchboard);
-            
-            let expo_vayoo = contract_state.vayoo_precisions as u32;
-            let base = 10 as u128;
-            let multiplicator_vayoo = base.pow(expo_vayoo);
-            let mut real_price=(price) as u128;
-    
-      
-            real_price=real_price.checked_mul(multiplicator_vayoo as u128).unwrap().checked_div(multiplicator_swithchboard).unwrap();
-
-
-            contract_state.ending_price = real_price as u64;
-        }
-
-        contract_state.is_settling = true;
-        contract_state.is_halted_deposit = true;
-        contract_state.is_halted_trading = true;
+        let contract_key = contract_state.key();
+        let ending_price = settlement::ending_price(
+            contract_state,
+            &contract_key,
+            &ctx.accounts.oracle,
+            ctx.remaining_accounts,
+            time_now,
+        )?;
+
+        // A fresh oracle price overrides any fallback price still in its challenge window
+        contract_state.enter_settlement(ending_price, time_now as u64);
         Ok(())
     } else {
         err!(ErrorCode::MaturityNotReached)
@@ -84,13 +39,21 @@ pub fn handle(ctx: Context<TriggerSettleMode>) -> Result<()> {
 #[derive(Accounts)]
 pub struct TriggerSettleMode<'info> {
     pub contract_authority: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(seeds = [ROLES_SEED], bump = roles.bump)]
+    pub roles: Box<Account<'info, Roles>>,
     #[account[
         mut,
-        seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.as_ref(), contract_authority.key().as_ref()],
+        seeds = [contract_state.name.as_ref(), contract_state.lcontract_mint.as_ref(), contract_state.authority.as_ref()],
+        constraint = roles.grants(Role::Settler, &contract_state.admin_authority, &contract_authority.key()) @ ErrorCode::Unauthorized,
         bump,
-        constraint = pyth_feed.key() == contract_state.oracle_feed_key || switchboard_feed.key() == contract_state.oracle_feed_key
     ]]
     pub contract_state: Box<Account<'info, ContractState>>,
-    pub switchboard_feed: AccountLoader<'info, AggregatorAccountData>,
-    pub pyth_feed: Account<'info, PriceFeed>,
+    /// CHECK: owner and layout are checked against the contract feed type by the oracle module
+    #[account(constraint = oracle::is_contract_feed(&contract_state, &oracle) @ ErrorCode::InvalidOraclefeed)]
+    pub oracle: UncheckedAccount<'info>,
 }
@@ -1,5 +1,3 @@
-use std::cmp::min;
-
 //libraries
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
@@ -7,7 +5,10 @@ use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 use crate::states::UserState;
 //local imports
 use crate::errors::ErrorCode;
+use crate::payoff;
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
+use crate::states::GlobalState;
 
 pub fn handle(ctx: Context<UserSettleLong>) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
@@ -31,37 +32,7 @@ pub fn handle(ctx: Context<UserSettleLong>) -> Result<()> {
     //1.Settle the long side
     if user_state.lcontract_bought_as_user > 0 {
         //for this condition, we should also check the amounts of tokens in the token accounts to double check
-        let adapted_contract_limiting_amplitude = contract_state
-            .limiting_amplitude
-;
-
-        let midrange = contract_state
-            .limiting_amplitude
-
-            .checked_div(2)
-            .unwrap();
-
-        let lower_bound = contract_state.starting_price.checked_sub(midrange).unwrap();
-        let upper_bound = contract_state.starting_price + midrange;
-        let mut final_price = contract_state.ending_price;
-        if final_price > upper_bound {
-            final_price = upper_bound;
-        }
-        if final_price < lower_bound {
-            final_price = lower_bound;
-        }
-
-        let mut pnl_lcontract = final_price.checked_sub(lower_bound).unwrap();
-        pnl_lcontract = min(pnl_lcontract, adapted_contract_limiting_amplitude);
-        msg!(&format!("pnl_lcontract  {}", pnl_lcontract));
-        //if payout_1_scontract>contract_limiting_bound_amplitude -> payout_1_lcontract=contract_limiting_bound_amplitude
-        //if payout_1_scontract<0 -> payout_1_lcontract=0
-        let gains_longer = user_state
-            .lcontract_bought_as_user
-            .checked_mul(pnl_lcontract)
-            .unwrap()
-            .checked_div(contract_state.oracle_price_multiplier)
-            .unwrap();
+        let gains_longer = payoff::compute(contract_state, user_state.lcontract_bought_as_user)?.long_payout.0;
 
         let cpi_accounts_transfer_pnl_long = Transfer {
             from: ctx.accounts.escrow_vault_collateral.to_account_info(),
@@ -88,7 +59,6 @@ pub fn handle(ctx: Context<UserSettleLong>) -> Result<()> {
         msg!("user settle burn: {}", user_state.lcontract_bought_as_user);
         token::burn(cpi_ctx, user_state.lcontract_bought_as_user)?;
 
-        let local_pyt_multiplier = contract_state.oracle_price_multiplier;
         let contract_state_m = &mut ctx.accounts.contract_state;
         //update user states
         contract_state_m.global_current_issued_lcontract = contract_state_m
@@ -106,11 +76,8 @@ pub fn handle(ctx: Context<UserSettleLong>) -> Result<()> {
         //Making sure the whole platform is well collateralized
         let global_final_issued_contract = contract_state_m.global_current_issued_lcontract;
 
-        let global_needed_collateral = global_final_issued_contract
-            .checked_mul(pnl_lcontract)
-            .unwrap()
-            .checked_div(local_pyt_multiplier)
-            .unwrap();
+        let global_needed_collateral =
+            payoff::compute(contract_state_m, global_final_issued_contract)?.long_payout.0;
 
         if global_needed_collateral > contract_state_m.global_current_locked_usdc {
             msg!("global_needed_collateral: {}", global_needed_collateral);
@@ -136,6 +103,12 @@ pub struct UserSettleLong<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         seeds = [contract_state.name.as_bytes(), contract_state.lcontract_mint.key().as_ref(), contract_state.authority.key().as_ref()],
@@ -4,8 +4,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 //local imports
+use crate::constants::GLOBAL_STATE_SEED;
 use crate::states::contract_state::ContractState;
 use crate::states::user_state::UserState;
+use crate::states::GlobalState;
 
 pub fn handle(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
     let user_state = &mut ctx.accounts.user_state;
@@ -30,8 +32,11 @@ pub fn handle(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
     token::transfer(cpi_ctx, amount)?;
 
     // Update State
-    user_state.usdc_deposited = user_state.usdc_deposited.saturating_sub(amount);
-    contract_state.current_tvl_usdc = contract_state.current_tvl_usdc.saturating_sub(amount);
+    // TVL counts deposits, gains withdrawn on top of them were never deposited
+    let deposit_withdrawn = amount.min(user_state.usdc_deposited);
+    user_state.usdc_deposited -= deposit_withdrawn;
+    contract_state.remove_tvl(deposit_withdrawn)?;
+    ctx.accounts.global_state.remove_tvl(deposit_withdrawn)?;
 
     user_state.usdc_free = user_state.usdc_free.saturating_sub(amount);
     Ok(())
@@ -42,6 +47,13 @@ pub struct WithdrawCollateral<'info> {
     #[account(mut)]
     pub user_authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         mut,
         associated_token::mint = contract_state.collateral_mint,
@@ -2,15 +2,22 @@
 use anchor_lang::prelude::*;
 
 //local imports
+pub mod attestation;
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod oracle;
+pub mod payoff;
+pub mod settlement;
 pub mod states;
 pub mod utils;
 pub mod types;
 
 // crates
 use crate::instructions::*;
+use crate::states::{AggregateSource, BasketComponent};
+use crate::types::{OracleConfig, PausableInstruction};
 use crate::utils::*;
 
 #[cfg(feature="dev")]
@@ -34,9 +41,93 @@ pub mod vayoo_contracts {
         create_global_state::handle(ctx, bump)
     }
 
+    /**
+     * Create an aggregate feed
+     * Contracts created with FeedType::Aggregate on it start and settle
+     * on the median of its fresh sources
+     *
+     * Should only be called by the super owner
+     */
+    pub fn initialize_aggregate_feed(
+        ctx: Context<InitializeAggregateFeed>,
+        name: String,
+        sources: Vec<AggregateSource>,
+        quorum: u8,
+    ) -> Result<()> {
+        initialize_aggregate_feed::handle(ctx, name, sources, quorum)
+    }
+
+    /**
+     * Create a ratio feed
+     * Contracts created with FeedType::Ratio on it start and settle
+     * on the price of its base feed in terms of its quote feed
+     *
+     * Should only be called by the super owner
+     */
+    pub fn initialize_ratio_feed(
+        ctx: Context<InitializeRatioFeed>,
+        name: String,
+        base: AggregateSource,
+        quote: AggregateSource,
+    ) -> Result<()> {
+        initialize_ratio_feed::handle(ctx, name, base, quote)
+    }
+
+    /**
+     * Create a basket feed
+     * Contracts created with FeedType::Basket on it start and settle
+     * on the weighted sum of its component feeds
+     *
+     * Should only be called by the super owner
+     */
+    pub fn initialize_basket_feed(
+        ctx: Context<InitializeBasketFeed>,
+        name: String,
+        components: Vec<BasketComponent>,
+    ) -> Result<()> {
+        initialize_basket_feed::handle(ctx, name, components)
+    }
+
+    /**
+     * Create a manual price feed, for localnet and offline tests
+     *
+     * Only available in dev builds, should only be called by the super owner
+     */
+    pub fn create_manual_feed(ctx: Context<CreateManualFeed>, name: String, expo: i32) -> Result<()> {
+        create_manual_feed::handle(ctx, name, expo)
+    }
+
+    /**
+     * Publish a price on a manual price feed
+     *
+     * Only available in dev builds, should only be called by the feed authority
+     */
+    pub fn set_manual_feed_price(ctx: Context<SetManualFeedPrice>, price: i64, conf: u64) -> Result<()> {
+        set_manual_feed_price::handle(ctx, price, conf)
+    }
+
+    /**
+     * Create an attestation signer set
+     * Contracts created with FeedType::SignedAttestation on it start and settle
+     * on prices signed by a threshold of its signers
+     *
+     * Should only be called by the super owner
+     */
+    pub fn initialize_attestation_signers(
+        ctx: Context<InitializeAttestationSigners>,
+        name: String,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        initialize_attestation_signers::handle(ctx, name, signers, threshold)
+    }
+
     /**
      * Initialize/Create the contract
      *
+     * `limiting_amplitude` is the width of the price band around the starting price,
+     * with PRICE_DECIMALS (6) decimals like every price of the contract: 2 USD is 2_000_000
+     *
      * Should only be called by the super owner
      */
     pub fn initialize_contract(
@@ -46,8 +137,17 @@ pub mod vayoo_contracts {
         ending_time: u64,
         limiting_amplitude: u64,
         feed_type: u8,
+        oracle_config: OracleConfig,
     ) -> Result<()> {
-        initialize_contract::handle(ctx, contract_name, bump, ending_time, limiting_amplitude, feed_type)
+        initialize_contract::handle(
+            ctx,
+            contract_name,
+            bump,
+            ending_time,
+            limiting_amplitude,
+            feed_type,
+            oracle_config,
+        )
     }
 
     /**
@@ -57,7 +157,10 @@ pub mod vayoo_contracts {
      *
      * One state per contract
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::InitializeUser)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn initialize_user(ctx: Context<InitUser>, bump: u8) -> Result<()> {
         init_user_account::handle(ctx, bump)
     }
@@ -65,7 +168,10 @@ pub mod vayoo_contracts {
     /**
      * Deposit Collateral (USDC) from user -> vault
      */
-    #[access_control(unrestricted_deposit_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::DepositCollateral)
+        unrestricted_deposit_phase(&ctx.accounts.contract_state)
+    )]
     pub fn deposit_collateral(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
         deposit_collateral::handle(ctx, amount)
     }
@@ -73,6 +179,7 @@ pub mod vayoo_contracts {
     /**
      * Withdraw Collateral (USDC) from vault -> user
      */
+    #[access_control(not_paused(&ctx.accounts.global_state, PausableInstruction::WithdrawCollateral))]
     pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
         withdraw_collateral::handle(ctx, amount)
     }
@@ -84,7 +191,10 @@ pub mod vayoo_contracts {
      * locks 2 * limiting amplitude * nb of tokens for minting - (free -> locked)
      * mints the required contracts
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::MintLContractMm)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn mint_l_contract_mm(ctx: Context<MintContractMm>, amount: u64) -> Result<()> {
         mint_lcontract_mm::handle(ctx, amount)
     }
@@ -96,7 +206,10 @@ pub mod vayoo_contracts {
      * unlocks 2 * limiting amplitude * nb of tokens for minting - (locked -> free)
      * burns the required contracts
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::BurnLContractMm)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn burn_l_contract_mm(ctx: Context<BurnContractMm>, amount: u64) -> Result<()> {
         burn_lcontract_mm::handle(ctx, amount)
     }
@@ -104,7 +217,10 @@ pub mod vayoo_contracts {
     /**
      * Long Contract
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::LongUser)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn long_user(
         ctx: Context<LongUser>,
         amount: u64,
@@ -117,7 +233,10 @@ pub mod vayoo_contracts {
     /**
      * Close Long Contract
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::CloseLongUser)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn close_long_user(
         ctx: Context<CloseLongUser>,
         amount: u64,
@@ -130,7 +249,10 @@ pub mod vayoo_contracts {
     /**
      * Short Contract
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::ShortUser)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn short_user(
         ctx: Context<ShortUser>,
         amount: u64,
@@ -143,7 +265,10 @@ pub mod vayoo_contracts {
     /**
      * Close Short Contract
      */
-    #[access_control(unrestricted_trading_phase(&ctx.accounts.contract_state))]
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::CloseShortUser)
+        unrestricted_trading_phase(&ctx.accounts.contract_state)
+    )]
     pub fn close_short_user(
         ctx: Context<CloseShortUser>,
         amount: u64,
@@ -156,20 +281,70 @@ pub mod vayoo_contracts {
     /**
      * Trigger Settle Mode
      *
-     * Can be called by anyone, checks whether current time > maturity time.
+     * Checks whether current time > maturity time.
      * If so, trigger settling mode on the contract state
+     * Should only be called by the settler or the contract admin authority
      *
      */
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::TriggerSettleMode)
+        not_in_emergency(&ctx.accounts.contract_state)
+    )]
     pub fn trigger_settle_mode(ctx: Context<TriggerSettleMode>) -> Result<()> {
         trigger_settle_mode::handle(ctx)
     }
 
+    /**
+     * Record Settlement Sample
+     *
+     * Can be called by anyone during the settlement window of a TWAP or median contract.
+     * Records the current oracle price in the contract settlement samples
+     *
+     */
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::RecordSettlementSample)
+    )]
+    pub fn record_settlement_sample(ctx: Context<RecordSettlementSample>) -> Result<()> {
+        record_settlement_sample::handle(ctx)
+    }
+
+    /**
+     * Finalize Settlement Proposal
+     *
+     * Can be called by anyone once the challenge window of the price proposed
+     * by the authority is over, if the oracle did not settle the contract meanwhile
+     *
+     */
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::FinalizeSettlementProposal)
+        not_in_emergency(&ctx.accounts.contract_state)
+    )]
+    pub fn finalize_settlement_proposal(ctx: Context<FinalizeSettlementProposal>) -> Result<()> {
+        finalize_settlement_proposal::handle(ctx)
+    }
+
+    /**
+     * Re-submit the settlement price
+     *
+     * Can be called by the contract authority or the guardian during the dispute window,
+     * replaces the ending price with a fresh read following the contract settlement mode
+     *
+     */
+    #[access_control(not_in_emergency(&ctx.accounts.contract_state))]
+    pub fn resubmit_settlement_price(ctx: Context<ResubmitSettlementPrice>) -> Result<()> {
+        resubmit_settlement_price::handle(ctx)
+    }
+
     /**
      * Admin settle shorts and mm
      *
      * Can be called by superuser only (for now)
      *
      */
+    #[access_control(
+        not_in_emergency(&ctx.accounts.contract_state)
+        finalized_settlement(&ctx.accounts.contract_state)
+    )]
     pub fn admin_settle(ctx: Context<AdminSettle>) -> Result<()> {
         admin_settle::handle(ctx)
     }
@@ -180,6 +355,11 @@ pub mod vayoo_contracts {
      * Can be called by user only
      *
      */
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::UserSettleLong)
+        not_in_emergency(&ctx.accounts.contract_state)
+        finalized_settlement(&ctx.accounts.contract_state)
+    )]
     pub fn user_settle_long(ctx: Context<UserSettleLong>) -> Result<()> {
         user_settle_long::handle(ctx)
     }
@@ -190,6 +370,11 @@ pub mod vayoo_contracts {
      * Can be called by MM only
      *
      */
+    #[access_control(
+        not_paused(&ctx.accounts.global_state, PausableInstruction::MmSettleLong)
+        not_in_emergency(&ctx.accounts.contract_state)
+        finalized_settlement(&ctx.accounts.contract_state)
+    )]
     pub fn mm_settle_long(ctx: Context<MmSettleLong>, amount: u64) -> Result<()> {
         mm_settle_long::handle(ctx, amount)
     }
@@ -197,18 +382,290 @@ pub mod vayoo_contracts {
     /**
      * Emergency withdraw
      *
+     * Once the contract is in emergency mode, refunds the free and locked collateral
+     * of the user, and burns the positions held in their vaults. The lcontracts burned are paid
+     * their pro-rata share of the escrow, which is empty before settlement. Can be called again,
+     * e.g. once lcontracts are pulled back from the pool.
+     * Not affected by the global pause: emergency mode is the gate
      */
     pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
         emergency_withdraw::handle(ctx)
     }
 
     /**
-     * Withdraw Collateral (USDC) from vault -> user
+     * Emergency redeem of lcontracts
+     *
+     * Once the contract is in emergency mode, burns the lcontracts of the holder's token account
+     * and pays their pro-rata share of the escrow, without a user account.
+     * Not affected by the global pause: emergency mode is the gate
+     */
+    pub fn emergency_redeem_lcontract(ctx: Context<EmergencyRedeemLcontract>) -> Result<()> {
+        emergency_redeem_lcontract::handle(ctx)
+    }
+
+    /**
+     * Update the limiting amplitude of the contract, with PRICE_DECIMALS (6) decimals
+     *
+     * Only until lcontracts are issued, positions are collateralized at the current amplitude.
+     * Should only be called by the contract authority or parameter admin, before maturity
      */
-     pub fn admin_sets_amplitude(ctx: Context<AdminSetsAmplitude>, amplitude_test: u64) -> Result<()> {
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_sets_amplitude(ctx: Context<AdminSetsAmplitude>, amplitude_test: u64) -> Result<()> {
         admin_sets_amplitude::handle(ctx, amplitude_test)
     }
 
+    /**
+     * Update the oracle staleness windows of the contract
+     *
+     * Only until lcontracts are issued, admin_queues_parameter changes them afterwards.
+     * Should only be called by the contract authority or parameter admin, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_sets_staleness(
+        ctx: Context<AdminSetsStaleness>,
+        max_staleness_init_secs: u64,
+        max_staleness_settle_secs: u64,
+    ) -> Result<()> {
+        admin_sets_staleness::handle(ctx, max_staleness_init_secs, max_staleness_settle_secs)
+    }
+
+    /**
+     * Set the open interest cap of the contract
+     *
+     * Caps the lcontracts issued by MM mints and user shorts, 0 removes the cap.
+     * Should only be called by the contract authority or parameter admin, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_sets_cap_product(ctx: Context<AdminSetsCapProduct>, cap_product: u64) -> Result<()> {
+        admin_sets_cap_product::handle(ctx, cap_product)
+    }
+
+    /**
+     * Set the TVL cap of the contract
+     *
+     * Deposits that would take the contract TVL over it fail, 0 removes the cap.
+     * Should only be called by the contract authority or parameter admin, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_sets_max_tvl(ctx: Context<AdminSetsMaxTvl>, max_tvl_usdc: u64) -> Result<()> {
+        admin_sets_max_tvl::handle(ctx, max_tvl_usdc)
+    }
+
+    /**
+     * Set the TVL cap of the protocol
+     *
+     * Deposits that would take the TVL of all contracts over it fail, 0 removes the cap.
+     * Should only be called by the global authority or parameter admin
+     */
+    pub fn admin_sets_max_total_tvl(
+        ctx: Context<AdminSetsMaxTotalTvl>,
+        max_total_tvl_usdc: u64,
+    ) -> Result<()> {
+        admin_sets_max_total_tvl::handle(ctx, max_total_tvl_usdc)
+    }
+
+    /**
+     * Queue a risk parameter change
+     *
+     * Once lcontracts are issued, risk parameters only change through this queue and
+     * apply after PARAMETER_TIMELOCK_SECS. A new change replaces the queued one.
+     * Should only be called by the contract authority or parameter admin, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_queues_parameter(
+        ctx: Context<AdminQueuesParameter>,
+        parameter: u8,
+        value: u64,
+    ) -> Result<()> {
+        admin_queues_parameter::handle(ctx, parameter, value)
+    }
+
+    /**
+     * Cancel the queued risk parameter change
+     *
+     * Should only be called by the contract authority or parameter admin
+     */
+    pub fn admin_cancels_parameter(ctx: Context<AdminCancelsParameter>) -> Result<()> {
+        admin_cancels_parameter::handle(ctx)
+    }
+
+    /**
+     * Apply the queued risk parameter change
+     *
+     * Can be called by anyone once the timelock is over, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn apply_queued_parameter(ctx: Context<ApplyQueuedParameter>) -> Result<()> {
+        apply_queued_parameter::handle(ctx)
+    }
+
+    /**
+     * Switch the contract to TWAP or median settlement
+     *
+     * Creates the ring buffer the settlement samples are recorded in.
+     * Should only be called by the contract authority, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn initialize_settlement_samples(
+        ctx: Context<InitializeSettlementSamples>,
+        settlement_mode: u8,
+        settlement_window_secs: u64,
+    ) -> Result<()> {
+        initialize_settlement_samples::handle(ctx, settlement_mode, settlement_window_secs)
+    }
+
+    /**
+     * Propose a fallback settlement price
+     *
+     * For when the oracle cannot settle the contract, only after the grace period
+     * following maturity. The price, with the decimals of the contract prices, becomes
+     * final after the challenge window unless trigger_settle_mode settles the contract
+     * on the oracle first
+     *
+     * Should only be called by the contract authority
+     */
+    #[access_control(not_in_emergency(&ctx.accounts.contract_state))]
+    pub fn admin_proposes_settlement_price(
+        ctx: Context<AdminProposesSettlementPrice>,
+        proposed_price: u64,
+    ) -> Result<()> {
+        admin_proposes_settlement_price::handle(ctx, proposed_price)
+    }
+
+    /**
+     * Update the dispute window of the contract, at least MIN_DISPUTE_WINDOW_SECS outside dev builds
+     *
+     * Only until lcontracts are issued, admin_queues_parameter changes it afterwards.
+     * Should only be called by the contract authority or parameter admin, before maturity
+     */
+    #[access_control(before_maturity(&ctx.accounts.contract_state))]
+    pub fn admin_sets_dispute_window(
+        ctx: Context<AdminSetsDisputeWindow>,
+        dispute_window_secs: u64,
+    ) -> Result<()> {
+        admin_sets_dispute_window::handle(ctx, dispute_window_secs)
+    }
+
+    /**
+     * Create the roles account, with no role granted
+     *
+     * Should only be called by the super owner
+     */
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        initialize_roles::handle(ctx)
+    }
+
+    /**
+     * Grant a Role to holder, replacing its previous holder
+     * The default key revokes the role
+     *
+     * Should only be called by the super owner
+     */
+    pub fn admin_sets_role(ctx: Context<AdminSetsRole>, role: u8, holder: Pubkey) -> Result<()> {
+        admin_sets_role::handle(ctx, role, holder)
+    }
+
+    /**
+     * Propose a new super owner, who becomes it by calling accept_global_authority
+     *
+     * Proposing the default key cancels the transfer.
+     * Should only be called by the super owner
+     */
+    pub fn admin_proposes_global_authority(
+        ctx: Context<AdminProposesGlobalAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        admin_proposes_global_authority::handle(ctx, new_authority)
+    }
+
+    /**
+     * Accept the super owner rights
+     *
+     * Should only be called by the proposed super owner
+     */
+    pub fn accept_global_authority(ctx: Context<AcceptGlobalAuthority>) -> Result<()> {
+        accept_global_authority::handle(ctx)
+    }
+
+    /**
+     * Propose a new admin authority for the contract, who gets the admin rights
+     * by calling accept_contract_authority. The contract PDA keeps its seeds
+     *
+     * Proposing the default key cancels the transfer.
+     * Should only be called by the contract admin authority
+     */
+    pub fn admin_proposes_contract_authority(
+        ctx: Context<AdminProposesContractAuthority>,
+        new_admin_authority: Pubkey,
+    ) -> Result<()> {
+        admin_proposes_contract_authority::handle(ctx, new_admin_authority)
+    }
+
+    /**
+     * Accept the admin rights of the contract
+     *
+     * Should only be called by the proposed admin authority
+     */
+    pub fn accept_contract_authority(ctx: Context<AcceptContractAuthority>) -> Result<()> {
+        accept_contract_authority::handle(ctx)
+    }
+
+    /**
+     * Resize a contract created before admin authorities existed to the current layout,
+     * and count its deposits in the protocol TVL
+     *
+     * Should only be called by the contract seed authority, once after upgrading the program
+     */
+    pub fn migrate_contract_state(ctx: Context<MigrateContractState>) -> Result<()> {
+        migrate_contract_state::handle(ctx)
+    }
+
+    /**
+     * Enable the emergency mode of the contract
+     *
+     * Halts the contract and stops its settlement for good,
+     * snapshots the escrow shared by emergency_withdraw and emergency_redeem_lcontract.
+     * Should only be called by the contract admin authority or the guardian
+     */
+    pub fn admin_sets_emergency_mode(ctx: Context<AdminSetsEmergencyMode>) -> Result<()> {
+        admin_sets_emergency_mode::handle(ctx)
+    }
+
+    /**
+     * Pause or unpause every user facing instruction of the protocol,
+     * except the PausableInstruction bits set in pause_allowlist
+     *
+     * Should only be called by the super owner
+     */
+    pub fn admin_sets_paused(
+        ctx: Context<AdminSetsPaused>,
+        paused: bool,
+        pause_allowlist: u64,
+    ) -> Result<()> {
+        admin_sets_paused::handle(ctx, paused, pause_allowlist)
+    }
+
+    /**
+     * Set or clear one of the halt flags of the contract, see HaltFlag
+     *
+     * Withdrawals and settlement are never halted, the halts of a contract in
+     * emergency mode cannot be cleared.
+     * Should only be called by the contract authority
+     */
+    pub fn admin_sets_halt(ctx: Context<AdminSetsHalt>, halt_flag: u8, halted: bool) -> Result<()> {
+        admin_sets_halt::handle(ctx, halt_flag, halted)
+    }
+
+    /**
+     * Update the guardian allowed to re-submit settlement prices
+     *
+     * Should only be called by the super owner
+     */
+    pub fn admin_sets_guardian(ctx: Context<AdminSetsGuardian>, guardian: Pubkey) -> Result<()> {
+        admin_sets_guardian::handle(ctx, guardian)
+    }
+
+    #[access_control(not_in_emergency(&ctx.accounts.contract_state))]
     pub fn admin_triggers_settle_mode(ctx: Context<AdminTriggersSettleMode>, test_settlement_price: u64) -> Result<()> {
         admin_triggers_settle_mode::handle(ctx, test_settlement_price)
     }
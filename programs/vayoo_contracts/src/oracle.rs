@@ -0,0 +1,801 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountDeserialize;
+use switchboard_v2::{AggregatorAccountData, SwitchboardDecimal, SWITCHBOARD_PROGRAM_ID};
+
+use crate::attestation;
+use crate::constants::{AGGREGATE_EXPO, BASKET_WEIGHT_DECIMALS, BPS_DENOMINATOR, PRICE_DECIMALS};
+use crate::errors::ErrorCode;
+use crate::states::{
+    AggregateFeed, BasketFeed, ContractState, ManualFeed, PriceFeed, PriceUpdateV2, PullFeed,
+    RatioFeed, VerificationLevel,
+};
+use crate::types::{FeedType, Price};
+
+/// Price read from an oracle, worth `price * 10^expo`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i128,
+    /// Confidence interval around `price`, with the same exponent
+    pub conf: u128,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Converts the price to a `Price` with `PRICE_DECIMALS` decimals, rounding down
+    pub fn normalized(&self) -> Result<Price> {
+        self.to_price(PRICE_DECIMALS)
+    }
+
+    /// Converts the price to a `Price` with `decimals` decimals, rounding down.
+    /// Negative exponents divide the mantissa, positive ones multiply it.
+    pub fn to_price(&self, decimals: u8) -> Result<Price> {
+        require!(self.price > 0, ErrorCode::InvalidOraclefeed);
+        let base: i128 = 10;
+        let value = self
+            .price
+            .checked_mul(
+                base.checked_pow(decimals as u32)
+                    .ok_or(ErrorCode::Overflow)?,
+            )
+            .ok_or(ErrorCode::Overflow)?;
+        let value = if self.expo < 0 {
+            let divisor = base
+                .checked_pow(self.expo.unsigned_abs())
+                .ok_or(ErrorCode::Overflow)?;
+            value / divisor
+        } else {
+            let factor = base
+                .checked_pow(self.expo as u32)
+                .ok_or(ErrorCode::Overflow)?;
+            value.checked_mul(factor).ok_or(ErrorCode::Overflow)?
+        };
+        let raw = u64::try_from(value).map_err(|_| error!(ErrorCode::Overflow))?;
+        Ok(Price::new(raw))
+    }
+
+    /// Fails if the confidence interval is wider than `max_confidence_bps` of the price
+    pub fn check_confidence(&self, max_confidence_bps: u64) -> Result<()> {
+        let max_conf = self
+            .price
+            .unsigned_abs()
+            .checked_mul(max_confidence_bps as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        let conf = self
+            .conf
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        if conf > max_conf {
+            msg!("Confidence {} too wide for price {}", self.conf, self.price);
+            return err!(ErrorCode::ConfidenceIntervalExceeded);
+        }
+        Ok(())
+    }
+
+    /// Same price expressed with `expo`, rounding down when precision is dropped
+    pub fn with_expo(&self, expo: i32) -> Result<OraclePrice> {
+        let base: i128 = 10;
+        let (price, conf) = if expo <= self.expo {
+            let factor = base
+                .checked_pow(self.expo.abs_diff(expo))
+                .ok_or(ErrorCode::Overflow)?;
+            (
+                self.price.checked_mul(factor).ok_or(ErrorCode::Overflow)?,
+                self.conf
+                    .checked_mul(factor as u128)
+                    .ok_or(ErrorCode::Overflow)?,
+            )
+        } else {
+            let divisor = base
+                .checked_pow(self.expo.abs_diff(expo))
+                .ok_or(ErrorCode::Overflow)?;
+            (self.price / divisor, self.conf / divisor as u128)
+        };
+        Ok(OraclePrice {
+            price,
+            conf,
+            expo,
+            publish_time: self.publish_time,
+        })
+    }
+}
+
+/// Median of `prices`, with the median confidence and the oldest publish time.
+/// The two middle values are averaged when there is an even number of prices.
+pub fn median_price(prices: &[OraclePrice]) -> Result<OraclePrice> {
+    require!(!prices.is_empty(), ErrorCode::OracleQuorumNotReached);
+    let prices = prices
+        .iter()
+        .map(|price| price.with_expo(AGGREGATE_EXPO))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut values: Vec<i128> = prices.iter().map(|price| price.price).collect();
+    let mut confs: Vec<u128> = prices.iter().map(|price| price.conf).collect();
+    values.sort_unstable();
+    confs.sort_unstable();
+    let middle = prices.len() / 2;
+    let (price, conf) = if prices.len() % 2 == 1 {
+        (values[middle], confs[middle])
+    } else {
+        (
+            (values[middle - 1] + values[middle]) / 2,
+            (confs[middle - 1] + confs[middle]) / 2,
+        )
+    };
+
+    Ok(OraclePrice {
+        price,
+        conf,
+        expo: AGGREGATE_EXPO,
+        publish_time: prices.iter().map(|price| price.publish_time).min().unwrap(),
+    })
+}
+
+/// Price of `base` in units of `quote`, both legs rescaled to `AGGREGATE_EXPO` first.
+/// Relative confidences add up, the oldest publish time is kept.
+pub fn ratio_price(base: &OraclePrice, quote: &OraclePrice) -> Result<OraclePrice> {
+    let base = base.with_expo(AGGREGATE_EXPO)?;
+    let quote = quote.with_expo(AGGREGATE_EXPO)?;
+    require!(quote.price > 0, ErrorCode::InvalidOraclefeed);
+
+    let unit = 10i128.pow(AGGREGATE_EXPO.unsigned_abs());
+    let price = base.price.checked_mul(unit).ok_or(ErrorCode::Overflow)? / quote.price;
+    // conf / price = base.conf / base.price + quote.conf / quote.price
+    let conf = base
+        .conf
+        .checked_mul(unit as u128)
+        .and_then(|conf| conf.checked_add(price.unsigned_abs().checked_mul(quote.conf)?))
+        .ok_or(ErrorCode::Overflow)?
+        / quote.price as u128;
+
+    Ok(OraclePrice {
+        price,
+        conf,
+        expo: AGGREGATE_EXPO,
+        publish_time: base.publish_time.min(quote.publish_time),
+    })
+}
+
+/// Sum of `prices` weighted by `weights`, which have `BASKET_WEIGHT_DECIMALS` decimals.
+/// Confidences add up, the oldest publish time is kept.
+pub fn basket_price(prices: &[OraclePrice], weights: &[u64]) -> Result<OraclePrice> {
+    require!(!prices.is_empty(), ErrorCode::InvalidOraclefeed);
+    let unit = 10i128.pow(BASKET_WEIGHT_DECIMALS);
+    let mut total = OraclePrice {
+        expo: AGGREGATE_EXPO,
+        publish_time: i64::MAX,
+        ..Default::default()
+    };
+    for (price, weight) in prices.iter().zip(weights) {
+        let price = price.with_expo(AGGREGATE_EXPO)?;
+        require!(price.price > 0, ErrorCode::InvalidOraclefeed);
+        let value = price
+            .price
+            .checked_mul(*weight as i128)
+            .ok_or(ErrorCode::Overflow)?;
+        let conf = price
+            .conf
+            .checked_mul(*weight as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        total.price = total.price.checked_add(value).ok_or(ErrorCode::Overflow)?;
+        total.conf = total.conf.checked_add(conf).ok_or(ErrorCode::Overflow)?;
+        total.publish_time = total.publish_time.min(price.publish_time);
+    }
+    total.price /= unit;
+    total.conf /= unit as u128;
+    Ok(total)
+}
+
+/// A price feed the contracts can start and settle on
+pub trait OracleSource {
+    /// Latest price, failing if it was published more than `max_staleness` seconds before `current_timestamp`
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice>;
+}
+
+impl OracleSource for PriceFeed {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        let price = self
+            .get_price_no_older_than(current_timestamp, max_staleness)
+            .ok_or(ErrorCode::PythOffline)?;
+        Ok(OraclePrice {
+            price: price.price as i128,
+            conf: price.conf as u128,
+            expo: price.expo,
+            publish_time: price.publish_time,
+        })
+    }
+}
+
+impl OracleSource for AggregatorAccountData {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        self.check_staleness(current_timestamp, max_staleness as i64)
+            .map_err(|_| error!(ErrorCode::StaleFeed))?;
+        let result = self.get_result()?;
+        let std_deviation = self.latest_confirmed_round.std_deviation;
+        Ok(OraclePrice {
+            price: result.mantissa,
+            conf: rescale(std_deviation, result.scale)?.unsigned_abs(),
+            expo: -(result.scale as i32),
+            publish_time: self.latest_confirmed_round.round_open_timestamp,
+        })
+    }
+}
+
+impl OracleSource for ManualFeed {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        require!(
+            current_timestamp.saturating_sub(self.publish_time) <= max_staleness as i64,
+            ErrorCode::StaleFeed
+        );
+        Ok(OraclePrice {
+            price: self.price as i128,
+            conf: self.conf as u128,
+            expo: self.expo,
+            publish_time: self.publish_time,
+        })
+    }
+}
+
+impl OracleSource for PriceUpdateV2 {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        require!(
+            self.verification_level == VerificationLevel::Full,
+            ErrorCode::PartiallyVerifiedUpdate
+        );
+        let message = &self.price_message;
+        require!(
+            current_timestamp.saturating_sub(message.publish_time) <= max_staleness as i64,
+            ErrorCode::StaleFeed
+        );
+        Ok(OraclePrice {
+            price: message.price as i128,
+            conf: message.conf as u128,
+            expo: message.exponent,
+            publish_time: message.publish_time,
+        })
+    }
+}
+
+impl OracleSource for PullFeed {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        require!(self.num_samples > 0, ErrorCode::InvalidOraclefeed);
+        require!(
+            current_timestamp.saturating_sub(self.last_update_timestamp) <= max_staleness as i64,
+            ErrorCode::StaleFeed
+        );
+        Ok(OraclePrice {
+            price: self.value,
+            conf: self.std_dev.unsigned_abs(),
+            expo: -(PullFeed::DECIMALS as i32),
+            publish_time: self.last_update_timestamp,
+        })
+    }
+}
+
+/// Switchboard V2 aggregator, read in place as the account is too large to copy
+struct SwitchboardFeed<'a, 'info>(&'a AccountInfo<'info>);
+
+impl OracleSource for SwitchboardFeed<'_, '_> {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        let data = self.0.try_borrow_data()?;
+        AggregatorAccountData::new_from_bytes(&data)?.get_price(current_timestamp, max_staleness)
+    }
+}
+
+/// Aggregate feed, with the accounts its sources are looked up in by key
+struct AggregateSource<'a, 'info> {
+    feed: AggregateFeed,
+    sources: &'a [AccountInfo<'info>],
+    contract: Pubkey,
+}
+
+impl OracleSource for AggregateSource<'_, '_> {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        // Missing, stale or invalid sources are left out of the median
+        let prices: Vec<OraclePrice> = self
+            .feed
+            .sources()
+            .iter()
+            .filter_map(|source| {
+                let account = self
+                    .sources
+                    .iter()
+                    .find(|account| account.key() == source.feed_key)?;
+                read_price(
+                    source.feed_type,
+                    account,
+                    &[],
+                    &self.contract,
+                    current_timestamp,
+                    max_staleness,
+                )
+                .map_err(|_| msg!("Aggregate source {} unavailable", source.feed_key))
+                .ok()
+            })
+            .collect();
+        msg!(
+            "{} of {} aggregate sources fresh, quorum {}",
+            prices.len(),
+            self.feed.sources_len,
+            self.feed.quorum
+        );
+        require!(
+            prices.len() >= self.feed.quorum as usize,
+            ErrorCode::OracleQuorumNotReached
+        );
+        median_price(&prices)
+    }
+}
+
+/// Price of the component feed `feed_key`, looked up in `sources`
+fn read_component(
+    sources: &[AccountInfo],
+    contract: &Pubkey,
+    feed_type: u8,
+    feed_key: Pubkey,
+    current_timestamp: i64,
+    max_staleness: u64,
+) -> Result<OraclePrice> {
+    let account = sources
+        .iter()
+        .find(|account| account.key() == feed_key)
+        .ok_or(ErrorCode::InvalidOraclefeed)?;
+    read_price(
+        feed_type,
+        account,
+        &[],
+        contract,
+        current_timestamp,
+        max_staleness,
+    )
+}
+
+/// Ratio feed, with the accounts its legs are looked up in by key
+struct RatioSource<'a, 'info> {
+    feed: RatioFeed,
+    sources: &'a [AccountInfo<'info>],
+    contract: Pubkey,
+}
+
+impl OracleSource for RatioSource<'_, '_> {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        // Each leg is checked for staleness on its own
+        let (base, quote) = (&self.feed.base, &self.feed.quote);
+        let base = read_component(
+            self.sources,
+            &self.contract,
+            base.feed_type,
+            base.feed_key,
+            current_timestamp,
+            max_staleness,
+        )?;
+        let quote = read_component(
+            self.sources,
+            &self.contract,
+            quote.feed_type,
+            quote.feed_key,
+            current_timestamp,
+            max_staleness,
+        )?;
+        msg!(
+            "Ratio legs {} (expo {}) / {} (expo {})",
+            base.price,
+            base.expo,
+            quote.price,
+            quote.expo
+        );
+        ratio_price(&base, &quote)
+    }
+}
+
+/// Basket feed, with the accounts its components are looked up in by key
+struct BasketSource<'a, 'info> {
+    feed: BasketFeed,
+    sources: &'a [AccountInfo<'info>],
+    contract: Pubkey,
+}
+
+impl OracleSource for BasketSource<'_, '_> {
+    fn get_price(&self, current_timestamp: i64, max_staleness: u64) -> Result<OraclePrice> {
+        // Every component must be present and fresh
+        let components = self.feed.components();
+        let prices = components
+            .iter()
+            .map(|component| {
+                read_component(
+                    self.sources,
+                    &self.contract,
+                    component.feed_type,
+                    component.feed_key,
+                    current_timestamp,
+                    max_staleness,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let weights: Vec<u64> = components
+            .iter()
+            .map(|component| component.weight)
+            .collect();
+        basket_price(&prices, &weights)
+    }
+}
+
+/// Key a contract stores to identify `oracle`: the Pyth feed id of pull oracle updates,
+/// which are posted to new accounts, the account itself otherwise
+pub fn feed_key(feed_type: u8, oracle: &AccountInfo) -> Result<Pubkey> {
+    match FeedType::try_from(feed_type)? {
+        FeedType::PythPull => Ok(load_pyth_pull(oracle)?.feed_key()),
+        _ => Ok(oracle.key()),
+    }
+}
+
+/// Whether `oracle` is the feed `contract_state` was created on
+pub fn is_contract_feed(contract_state: &ContractState, oracle: &AccountInfo) -> bool {
+    matches!(
+        feed_key(contract_state.oracle_feed_type, oracle),
+        Ok(key) if key == contract_state.oracle_feed_key
+    )
+}
+
+/// Loads `oracle` as a feed of `feed_type`, after checking it is owned by that oracle's program.
+/// For an aggregate, ratio or basket feed, its sources are looked up by key in `sources`,
+/// for a signed attestation, the instructions sysvar and attestations of `contract`.
+pub fn load_source<'a, 'info, 'sources>(
+    feed_type: u8,
+    oracle: &'a AccountInfo<'info>,
+    sources: &'a [AccountInfo<'sources>],
+    contract: &Pubkey,
+) -> Result<Box<dyn OracleSource + 'a>> {
+    match FeedType::try_from(feed_type)? {
+        FeedType::Pyth => Ok(Box::new(load_pyth(oracle)?)),
+        FeedType::Switchboard => Ok(Box::new(load_switchboard(oracle)?)),
+        FeedType::Aggregate => Ok(Box::new(load_aggregate(oracle, sources, contract)?)),
+        FeedType::Manual => Ok(Box::new(load_manual(oracle)?)),
+        FeedType::PythPull => Ok(Box::new(load_pyth_pull(oracle)?)),
+        FeedType::SwitchboardOnDemand => Ok(Box::new(load_switchboard_on_demand(oracle)?)),
+        FeedType::SignedAttestation => Ok(Box::new(attestation::load_attestation(
+            oracle, sources, contract,
+        )?)),
+        FeedType::Ratio => Ok(Box::new(load_ratio(oracle, sources, contract)?)),
+        FeedType::Basket => Ok(Box::new(load_basket(oracle, sources, contract)?)),
+        FeedType::Unknown => err!(ErrorCode::InvalidFeedType),
+    }
+}
+
+/// Latest price of `oracle`, a feed of `feed_type`
+pub fn read_price(
+    feed_type: u8,
+    oracle: &AccountInfo,
+    sources: &[AccountInfo],
+    contract: &Pubkey,
+    current_timestamp: i64,
+    max_staleness: u64,
+) -> Result<OraclePrice> {
+    load_source(feed_type, oracle, sources, contract)?.get_price(current_timestamp, max_staleness)
+}
+
+fn load_pyth(oracle: &AccountInfo) -> Result<PriceFeed> {
+    require_keys_eq!(
+        *oracle.owner,
+        PriceFeed::owner(),
+        ErrorCode::InvalidOraclefeed
+    );
+    let data = oracle.try_borrow_data()?;
+    PriceFeed::try_deserialize_unchecked(&mut &data[..])
+}
+
+fn load_switchboard<'a, 'info>(
+    oracle: &'a AccountInfo<'info>,
+) -> Result<SwitchboardFeed<'a, 'info>> {
+    require_keys_eq!(
+        *oracle.owner,
+        SWITCHBOARD_PROGRAM_ID,
+        ErrorCode::InvalidSwitchboardAccount
+    );
+    Ok(SwitchboardFeed(oracle))
+}
+
+fn load_aggregate<'a, 'info>(
+    oracle: &AccountInfo,
+    sources: &'a [AccountInfo<'info>],
+    contract: &Pubkey,
+) -> Result<AggregateSource<'a, 'info>> {
+    require_keys_eq!(*oracle.owner, crate::ID, ErrorCode::InvalidOraclefeed);
+    let data = oracle.try_borrow_data()?;
+    let feed = AggregateFeed::try_deserialize(&mut &data[..])?;
+    Ok(AggregateSource {
+        feed,
+        sources,
+        contract: *contract,
+    })
+}
+
+fn load_ratio<'a, 'info>(
+    oracle: &AccountInfo,
+    sources: &'a [AccountInfo<'info>],
+    contract: &Pubkey,
+) -> Result<RatioSource<'a, 'info>> {
+    require_keys_eq!(*oracle.owner, crate::ID, ErrorCode::InvalidOraclefeed);
+    let data = oracle.try_borrow_data()?;
+    let feed = RatioFeed::try_deserialize(&mut &data[..])?;
+    Ok(RatioSource {
+        feed,
+        sources,
+        contract: *contract,
+    })
+}
+
+fn load_basket<'a, 'info>(
+    oracle: &AccountInfo,
+    sources: &'a [AccountInfo<'info>],
+    contract: &Pubkey,
+) -> Result<BasketSource<'a, 'info>> {
+    require_keys_eq!(*oracle.owner, crate::ID, ErrorCode::InvalidOraclefeed);
+    let data = oracle.try_borrow_data()?;
+    let feed = BasketFeed::try_deserialize(&mut &data[..])?;
+    Ok(BasketSource {
+        feed,
+        sources,
+        contract: *contract,
+    })
+}
+
+fn load_manual(oracle: &AccountInfo) -> Result<ManualFeed> {
+    require!(cfg!(feature = "dev"), ErrorCode::NoTestInProd);
+    require_keys_eq!(*oracle.owner, crate::ID, ErrorCode::InvalidOraclefeed);
+    let data = oracle.try_borrow_data()?;
+    ManualFeed::try_deserialize(&mut &data[..])
+}
+
+fn load_pyth_pull(oracle: &AccountInfo) -> Result<PriceUpdateV2> {
+    require_keys_eq!(
+        *oracle.owner,
+        PriceUpdateV2::owner(),
+        ErrorCode::InvalidOraclefeed
+    );
+    let data = oracle.try_borrow_data()?;
+    PriceUpdateV2::try_deserialize(&mut &data[..])
+}
+
+fn load_switchboard_on_demand(oracle: &AccountInfo) -> Result<PullFeed> {
+    require_keys_eq!(
+        *oracle.owner,
+        PullFeed::owner(),
+        ErrorCode::InvalidSwitchboardAccount
+    );
+    let data = oracle.try_borrow_data()?;
+    PullFeed::try_deserialize(&mut &data[..])
+}
+
+// Mantissa of `decimal` expressed with `scale` decimals
+fn rescale(decimal: SwitchboardDecimal, scale: u32) -> Result<i128> {
+    let base: i128 = 10;
+    if decimal.scale <= scale {
+        let factor = base
+            .checked_pow(scale - decimal.scale)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(decimal
+            .mantissa
+            .checked_mul(factor)
+            .ok_or(ErrorCode::Overflow)?)
+    } else {
+        let divisor = base
+            .checked_pow(decimal.scale - scale)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(decimal.mantissa / divisor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::{pull_feed, PriceFeedMessage};
+
+    fn oracle_price(price: i128, expo: i32) -> OraclePrice {
+        OraclePrice {
+            price,
+            expo,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalizes_negative_exponents() {
+        // 1234.567890123456 published with every exponent from -12 to 0
+        let digits: i128 = 1_234_567_890_123_456;
+        for expo in -12..=0 {
+            let mantissa = digits / 10i128.pow((12 + expo) as u32);
+            let normalized = oracle_price(mantissa, expo).normalized().unwrap();
+
+            let kept_decimals = (-expo).min(PRICE_DECIMALS as i32) as u32;
+            let unit = 10u64.pow(PRICE_DECIMALS as u32 - kept_decimals);
+            assert_eq!(
+                normalized.raw(),
+                1_234_567_890 / unit * unit,
+                "expo {}",
+                expo
+            );
+        }
+    }
+
+    #[test]
+    fn normalizes_positive_exponents() {
+        assert_eq!(
+            oracle_price(12, 3).normalized().unwrap(),
+            Price::new(12_000_000_000)
+        );
+    }
+
+    #[test]
+    fn converts_to_contract_decimals() {
+        let price = oracle_price(2_712_345_678_901, -8);
+        assert_eq!(price.to_price(8).unwrap(), Price::new(2_712_345_678_901));
+        assert_eq!(price.to_price(6).unwrap(), Price::new(27_123_456_789));
+    }
+
+    #[test]
+    fn checks_confidence_against_bps_of_price() {
+        let price = OraclePrice {
+            price: 2_000_000,
+            conf: 1_000,
+            expo: -6,
+            publish_time: 0,
+        };
+        assert!(price.check_confidence(5).is_ok());
+        assert!(price.check_confidence(4).is_err());
+        assert!(price.check_confidence(0).is_err());
+        assert!(OraclePrice { conf: 0, ..price }.check_confidence(0).is_ok());
+    }
+
+    #[test]
+    fn takes_the_median_across_exponents() {
+        let pyth = OraclePrice {
+            price: 2_000_000_000_000,
+            conf: 100_000_000,
+            expo: -8,
+            publish_time: 10,
+        };
+        let switchboard = OraclePrice {
+            price: 20_100_000_000_000_000_000_000,
+            conf: 3_000_000_000_000_000_000,
+            expo: -18,
+            publish_time: 12,
+        };
+        let outlier = OraclePrice {
+            price: 35_000,
+            conf: 0,
+            expo: 0,
+            publish_time: 11,
+        };
+
+        let median = median_price(&[pyth, switchboard, outlier]).unwrap();
+        assert_eq!(median.expo, AGGREGATE_EXPO);
+        assert_eq!(median.normalized().unwrap(), Price::new(20_100_000_000));
+        assert_eq!(median.conf, 1_000_000_000_000);
+        assert_eq!(median.publish_time, 10);
+
+        let median = median_price(&[pyth, switchboard]).unwrap();
+        assert_eq!(median.normalized().unwrap(), Price::new(20_050_000_000));
+        assert!(median_price(&[]).is_err());
+    }
+
+    #[test]
+    fn divides_legs_across_exponents() {
+        // ETH/USD 2000 at expo -8, BTC/USD 40000 at expo -18
+        let eth = OraclePrice {
+            price: 200_000_000_000,
+            conf: 100_000_000,
+            expo: -8,
+            publish_time: 20,
+        };
+        let btc = OraclePrice {
+            price: 40_000_000_000_000_000_000_000,
+            conf: 40_000_000_000_000_000_000,
+            expo: -18,
+            publish_time: 15,
+        };
+
+        let ratio = ratio_price(&eth, &btc).unwrap();
+        assert_eq!(ratio.expo, AGGREGATE_EXPO);
+        assert_eq!(ratio.normalized().unwrap(), Price::new(50_000));
+        // 0.05% + 0.1% of 0.05
+        assert_eq!(ratio.conf, 75_000_000);
+        assert_eq!(ratio.publish_time, 15);
+
+        assert!(ratio_price(&eth, &oracle_price(0, -8)).is_err());
+    }
+
+    #[test]
+    fn sums_weighted_components() {
+        // 0.5 ETH at 2000 and 10 SOL at 20, at different exponents
+        let eth = OraclePrice {
+            price: 200_000_000_000,
+            conf: 100_000_000,
+            expo: -8,
+            publish_time: 20,
+        };
+        let sol = OraclePrice {
+            price: 20_000_000_000_000_000_000,
+            conf: 10_000_000_000_000_000,
+            expo: -18,
+            publish_time: 25,
+        };
+
+        let basket = basket_price(&[eth, sol], &[500_000, 10_000_000]).unwrap();
+        assert_eq!(basket.expo, AGGREGATE_EXPO);
+        assert_eq!(basket.normalized().unwrap(), Price::new(1_200_000_000));
+        assert_eq!(basket.conf, 600_000_000_000);
+        assert_eq!(basket.publish_time, 20);
+
+        assert!(basket_price(&[], &[]).is_err());
+        assert!(basket_price(&[oracle_price(0, -8)], &[1_000_000]).is_err());
+    }
+
+    #[test]
+    fn reads_manual_feeds_within_staleness() {
+        let feed = ManualFeed {
+            price: 2_500_000,
+            conf: 1_000,
+            expo: -5,
+            publish_time: 100,
+            ..Default::default()
+        };
+        let price = feed.get_price(160, 60).unwrap();
+        assert_eq!(price.normalized().unwrap(), Price::new(25_000_000));
+        assert_eq!(price.conf, 1_000);
+        assert!(feed.get_price(161, 60).is_err());
+    }
+
+    #[test]
+    fn reads_fully_verified_price_updates_only() {
+        let mut update = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id: [7; 32],
+                price: 6_543_210_000,
+                conf: 3_000_000,
+                exponent: -8,
+                publish_time: 1_000,
+                prev_publish_time: 999,
+                ema_price: 6_543_000_000,
+                ema_conf: 2_000_000,
+            },
+            posted_slot: 42,
+        };
+        let price = update.get_price(1_030, 30).unwrap();
+        assert_eq!(price.normalized().unwrap(), Price::new(65_432_100));
+        assert_eq!(price.conf, 3_000_000);
+        assert!(update.get_price(1_031, 30).is_err());
+
+        update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        assert!(update.get_price(1_030, 30).is_err());
+    }
+
+    #[test]
+    fn reads_on_demand_results_with_18_decimals() {
+        let data = pull_feed::tests::fixture(
+            65_432_123_456_789_000_000_000,
+            12_000_000_000_000_000,
+            5,
+            1_000,
+        );
+        let feed = PullFeed::try_deserialize(&mut &data[..]).unwrap();
+        let price = feed.get_price(1_060, 60).unwrap();
+        assert_eq!(price.normalized().unwrap(), Price::new(65_432_123_456));
+        assert_eq!(price.conf, 12_000_000_000_000_000);
+        assert!(feed.get_price(1_061, 60).is_err());
+
+        let data = pull_feed::tests::fixture(1, 0, 0, 1_000);
+        let feed = PullFeed::try_deserialize(&mut &data[..]).unwrap();
+        assert!(feed.get_price(1_000, 60).is_err());
+    }
+
+    #[test]
+    fn rejects_non_positive_and_overflowing_prices() {
+        assert!(oracle_price(0, -8).normalized().is_err());
+        assert!(oracle_price(-5, -8).normalized().is_err());
+        assert!(oracle_price(1, 30).normalized().is_err());
+        assert!(oracle_price(i64::MAX as i128, 0).normalized().is_err());
+    }
+}
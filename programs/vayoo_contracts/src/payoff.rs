@@ -0,0 +1,198 @@
+use std::cmp::min;
+
+use anchor_lang::prelude::*;
+
+use crate::states::ContractState;
+use crate::types::{Amount, Price, Quantity, Rounding};
+
+/// Settlement amounts for a quantity of contracts, in collateral native units
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Payoff {
+    /// Collateral owed to the holder of the lcontracts
+    pub long_payout: Amount,
+    /// Collateral handed back to the writer of the scontracts
+    pub short_payout: Amount,
+    /// Collateral moving through the escrow: in from the short side, out to the long side
+    pub escrow_delta: Amount,
+}
+
+/// Computes the settlement of `quantity` contracts at `contract_state.ending_price`,
+/// for a position that locked the full band per contract rounded up
+///
+/// The band is `starting_price +/- limiting_amplitude / 2`, the ending price is clamped into it,
+/// and each contract pays the long side its distance from the lower bound.
+/// The short side forfeits that distance into the escrow, rounded up, and gets back the rest
+/// of what it locked, so `escrow_delta + short_payout` always equals the locked collateral.
+/// The long side receives it rounded down: the remainder `escrow_delta - long_payout`,
+/// 0 or 1 native unit per settlement, stays in the escrow, so the escrow covers the longs
+/// however positions are split.
+pub fn compute(contract_state: &ContractState, quantity: u64) -> Result<Payoff> {
+    let locked_collateral = contract_state.locked_collateral(Quantity(quantity), Rounding::Up)?;
+    compute_locked(contract_state, quantity, locked_collateral)
+}
+
+/// Same as `compute`, for a short position that recorded `locked_collateral` for its
+/// `quantity` contracts. Positions opened before locks rounded up recorded up to 1 native unit
+/// less than `compute` assumes, what they forfeit is capped at what they locked.
+pub fn compute_locked(
+    contract_state: &ContractState,
+    quantity: u64,
+    locked_collateral: Amount,
+) -> Result<Payoff> {
+    let amplitude = contract_state.limiting_amplitude;
+    let quantity = Quantity(quantity);
+
+    let midrange = Price::new(amplitude.raw() / 2);
+    let lower_bound = contract_state.starting_price.checked_sub(midrange)?;
+    let upper_bound = contract_state.starting_price.checked_add(midrange)?;
+    let final_price = contract_state.ending_price.clamp(lower_bound, upper_bound);
+
+    let pnl_lcontract = min(final_price.checked_sub(lower_bound)?, amplitude);
+
+    let long_payout = contract_state.collateral_for(pnl_lcontract, quantity, Rounding::Down)?;
+    let escrow_delta = min(
+        contract_state.collateral_for(pnl_lcontract, quantity, Rounding::Up)?,
+        locked_collateral,
+    );
+    let short_payout = locked_collateral.checked_sub(escrow_delta)?;
+
+    Ok(Payoff {
+        long_payout,
+        short_payout,
+        escrow_delta,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn contract(starting_price: u64, ending_price: u64, limiting_amplitude: u64) -> ContractState {
+        ContractState {
+            starting_price: Price::new(starting_price),
+            ending_price: Price::new(ending_price),
+            limiting_amplitude: Price::new(limiting_amplitude),
+            vayoo_precisions: 6,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn settles_at_starting_price() {
+        let payoff = compute(
+            &contract(20_000_000_000, 20_000_000_000, 2_000_000_000),
+            3_000_000,
+        )
+        .unwrap();
+        assert_eq!(payoff.long_payout, Amount(3_000_000_000));
+        assert_eq!(payoff.short_payout, Amount(3_000_000_000));
+        assert_eq!(payoff.escrow_delta, Amount(3_000_000_000));
+    }
+
+    #[test]
+    fn clamps_above_upper_bound() {
+        let payoff = compute(
+            &contract(20_000_000_000, 99_000_000_000, 2_000_000_000),
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(payoff.long_payout, Amount(2_000_000_000));
+        assert_eq!(payoff.short_payout, Amount(0));
+    }
+
+    #[test]
+    fn clamps_below_lower_bound() {
+        let payoff = compute(&contract(20_000_000_000, 0, 2_000_000_000), 1_000_000).unwrap();
+        assert_eq!(payoff.long_payout, Amount(0));
+        assert_eq!(payoff.short_payout, Amount(2_000_000_000));
+        assert_eq!(payoff.escrow_delta, Amount(0));
+    }
+
+    #[test]
+    fn zero_quantity_pays_nothing() {
+        let payoff = compute(&contract(20_000_000_000, 21_000_000_000, 2_000_000_000), 0).unwrap();
+        assert_eq!(payoff, Payoff::default());
+    }
+
+    #[test]
+    fn rejects_band_below_zero() {
+        assert!(compute(&contract(1_000, 1_000, 4_000), 1).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_decimals() {
+        let mut state = contract(20_000_000_000, 20_000_000_000, 2_000_000_000);
+        state.vayoo_precisions = u8::MAX;
+        assert!(compute(&state, 1).is_err());
+    }
+
+    #[test]
+    fn leaves_the_rounding_remainder_in_the_escrow() {
+        // 3 contracts worth 0.500001 each: the short forfeits 2, the long receives 1
+        let payoff = compute(&contract(1_000_000, 1_000_001, 1_000_000), 3).unwrap();
+        assert_eq!(payoff.escrow_delta, Amount(2));
+        assert_eq!(payoff.long_payout, Amount(1));
+        assert_eq!(payoff.short_payout, Amount(1));
+    }
+
+    #[test]
+    fn caps_legacy_positions_at_what_they_locked() {
+        // Locked 1, rounded down, for 3 contracts worth 0.5 each: rounding up would forfeit 2
+        let state = contract(1_000_000, 2_000_000, 500_000);
+        let payoff = compute_locked(&state, 3, Amount(1)).unwrap();
+        assert_eq!(payoff.escrow_delta, Amount(1));
+        assert_eq!(payoff.short_payout, Amount(0));
+    }
+
+    proptest! {
+        // For any band, price and quantity, the short side's forfeit and refund add up to the
+        // collateral it locked, and the long side receives the forfeit minus at most 1 unit
+        #[test]
+        fn payouts_add_up_to_locked_collateral(
+            amplitude in 0..10_000_000_000u64,
+            offset in 0..100_000_000_000u64,
+            ending_price in 0..250_000_000_000u64,
+            quantity in 0..1_000_000_000u64,
+            decimals in 0..10u8,
+        ) {
+            let mut state = contract(amplitude / 2 + offset, ending_price, amplitude);
+            state.vayoo_precisions = decimals;
+
+            let payoff = compute(&state, quantity).unwrap();
+            let locked = state.locked_collateral(Quantity(quantity), Rounding::Up).unwrap();
+            let remainder = payoff.escrow_delta.checked_sub(payoff.long_payout).unwrap();
+            prop_assert_eq!(payoff.escrow_delta.checked_add(payoff.short_payout).unwrap(), locked);
+            prop_assert!(remainder <= Amount(1));
+            prop_assert_eq!(
+                payoff.long_payout.0 + payoff.short_payout.0 + remainder.0,
+                locked.0
+            );
+        }
+
+        // However the shorts and longs of the same contracts are split into positions,
+        // what the shorts forfeit covers what the longs receive
+        #[test]
+        fn escrow_covers_longs_across_splits(
+            amplitude in 0..10_000_000_000u64,
+            offset in 0..100_000_000_000u64,
+            ending_price in 0..250_000_000_000u64,
+            quantity in 0..1_000_000_000u64,
+            short_split in 0..1_000_000_000u64,
+            long_split in 0..1_000_000_000u64,
+            decimals in 0..10u8,
+        ) {
+            let mut state = contract(amplitude / 2 + offset, ending_price, amplitude);
+            state.vayoo_precisions = decimals;
+            let short_split = short_split % (quantity + 1);
+            let long_split = long_split % (quantity + 1);
+
+            let escrow_in = compute(&state, short_split).unwrap().escrow_delta.0
+                + compute(&state, quantity - short_split).unwrap().escrow_delta.0;
+            let escrow_out = compute(&state, long_split).unwrap().long_payout.0
+                + compute(&state, quantity - long_split).unwrap().long_payout.0;
+            prop_assert!(escrow_in >= escrow_out);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+
+use crate::attestation;
+use crate::errors::ErrorCode;
+use crate::oracle;
+use crate::states::{median, time_weighted_average, ContractState, SettlementSamples};
+use crate::types::{FeedType, Price, SettlementMode};
+
+/// Ending price of `contract_state` at `time_now`, following its settlement mode.
+/// Spot contracts read `oracle`, sampled contracts pass their settlement samples
+/// as the first remaining account. Records who signed the price of an attested contract.
+pub fn ending_price(
+    contract_state: &mut ContractState,
+    contract_key: &Pubkey,
+    oracle: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+    time_now: i64,
+) -> Result<Price> {
+    match SettlementMode::try_from(contract_state.settlement_mode)? {
+        SettlementMode::Spot => {
+            let oracle_price =
+                if contract_state.oracle_feed_type == FeedType::SignedAttestation as u8 {
+                    let verified = attestation::read_attestation(
+                        oracle,
+                        remaining_accounts,
+                        contract_key,
+                        time_now,
+                        contract_state.settle_staleness(),
+                    )?;
+                    contract_state.attestation_signers_mask = verified.signers_mask;
+                    verified.oracle_price()
+                } else {
+                    oracle::read_price(
+                        contract_state.oracle_feed_type,
+                        oracle,
+                        remaining_accounts,
+                        contract_key,
+                        time_now,
+                        contract_state.settle_staleness(),
+                    )?
+                };
+            msg!(
+                "Settling at price: {}, expo: {}",
+                oracle_price.price,
+                oracle_price.expo
+            );
+            // Contracts created before the limit existed have it unset
+            if contract_state.max_confidence_bps > 0 {
+                oracle_price.check_confidence(contract_state.max_confidence_bps)?;
+            }
+            oracle_price.to_price(contract_state.vayoo_precisions)
+        }
+        settlement_mode => {
+            let samples_info = remaining_accounts
+                .first()
+                .ok_or(ErrorCode::NoSettlementSamples)?;
+            require_keys_eq!(*samples_info.owner, crate::ID, ErrorCode::Invalid);
+            let settlement_samples =
+                SettlementSamples::try_deserialize(&mut &samples_info.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                settlement_samples.contract_state,
+                *contract_key,
+                ErrorCode::Invalid
+            );
+
+            let samples = settlement_samples.since(contract_state.settlement_window_start());
+            let ending_price = if settlement_mode == SettlementMode::Twap {
+                time_weighted_average(&samples, contract_state.ending_time as i64)
+            } else {
+                median(&samples)
+            };
+            let ending_price = ending_price.ok_or(ErrorCode::NoSettlementSamples)?;
+            msg!(
+                "Settling at price: {} from {} samples",
+                ending_price.raw(),
+                samples.len()
+            );
+            Ok(ending_price)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::ManualFeed;
+    use crate::types::FeedType;
+
+    /// Serialized manual feed at 21.5, published at 1_000
+    fn manual_feed_data() -> Vec<u8> {
+        let feed = ManualFeed {
+            price: 2_150_000,
+            conf: 100,
+            expo: -5,
+            publish_time: 1_000,
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        feed.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Band of 20 to 22 with PRICE_DECIMALS
+    fn manual_contract() -> ContractState {
+        ContractState {
+            oracle_feed_type: FeedType::Manual as u8,
+            vayoo_precisions: 6,
+            oracle_price_multiplier: 1_000_000,
+            starting_price: Price::new(21_000_000),
+            limiting_amplitude: Price::new(2_000_000),
+            max_staleness_settle_secs: 60,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn settles_from_a_manual_feed() {
+        use crate::payoff;
+        use crate::types::Amount;
+
+        let mut data = manual_feed_data();
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let oracle = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        let mut contract_state = manual_contract();
+        let contract_key = Pubkey::new_unique();
+        contract_state.ending_price =
+            ending_price(&mut contract_state, &contract_key, &oracle, &[], 1_030).unwrap();
+        assert_eq!(contract_state.ending_price, Price::new(21_500_000));
+
+        let payoff = payoff::compute(&contract_state, 2_000_000).unwrap();
+        assert_eq!(payoff.long_payout, Amount(3_000_000));
+        assert_eq!(payoff.short_payout, Amount(1_000_000));
+
+        // Past the settlement staleness window the feed is rejected
+        assert!(ending_price(&mut contract_state, &contract_key, &oracle, &[], 1_061).is_err());
+    }
+
+    #[cfg(not(feature = "dev"))]
+    #[test]
+    fn rejects_manual_feeds_outside_dev_builds() {
+        let mut data = manual_feed_data();
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let oracle = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        let mut contract_state = manual_contract();
+        let price = ending_price(
+            &mut contract_state,
+            &Pubkey::new_unique(),
+            &oracle,
+            &[],
+            1_030,
+        );
+        assert_eq!(price.unwrap_err(), ErrorCode::NoTestInProd.into());
+    }
+}
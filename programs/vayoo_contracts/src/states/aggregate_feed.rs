@@ -0,0 +1,32 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_AGGREGATE_SOURCES;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AggregateSource {
+    /// `FeedType` of the source, Pyth, Switchboard or Switchboard On-Demand
+    pub feed_type: u8,
+    pub feed_key: Pubkey,
+}
+
+/// Set of oracle feeds a contract with `FeedType::Aggregate` starts and settles on the median of
+#[account]
+#[derive(Default)]
+pub struct AggregateFeed {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    /// Fresh sources needed for a price to be produced
+    pub quorum: u8,
+    pub sources_len: u8,
+    pub sources: [AggregateSource; MAX_AGGREGATE_SOURCES],
+
+    pub reserved: [u64; 8],
+}
+
+impl AggregateFeed {
+    pub fn sources(&self) -> &[AggregateSource] {
+        &self.sources[..self.sources_len as usize]
+    }
+}
@@ -0,0 +1,26 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_ATTESTATION_SIGNERS;
+
+/// Keys allowed to sign prices of contracts with `FeedType::SignedAttestation`.
+/// Immutable once created, so the signers mask recorded by contracts stays meaningful.
+#[account]
+#[derive(Default)]
+pub struct AttestationSigners {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    /// Distinct signers needed for an attested price to be accepted
+    pub threshold: u8,
+    pub signers_len: u8,
+    pub signers: [Pubkey; MAX_ATTESTATION_SIGNERS],
+
+    pub reserved: [u64; 8],
+}
+
+impl AttestationSigners {
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..self.signers_len as usize]
+    }
+}
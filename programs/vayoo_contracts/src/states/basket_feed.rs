@@ -0,0 +1,33 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_BASKET_COMPONENTS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BasketComponent {
+    /// `FeedType` of the component, Pyth, Switchboard or Switchboard On-Demand
+    pub feed_type: u8,
+    pub feed_key: Pubkey,
+    /// Units of the feed underlying held by the basket, with `BASKET_WEIGHT_DECIMALS` decimals
+    pub weight: u64,
+}
+
+/// Index a contract with `FeedType::Basket` starts and settles on,
+/// worth the weighted sum of its component prices
+#[account]
+#[derive(Default)]
+pub struct BasketFeed {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    pub components_len: u8,
+    pub components: [BasketComponent; MAX_BASKET_COMPONENTS],
+
+    pub reserved: [u64; 8],
+}
+
+impl BasketFeed {
+    pub fn components(&self) -> &[BasketComponent] {
+        &self.components[..self.components_len as usize]
+    }
+}
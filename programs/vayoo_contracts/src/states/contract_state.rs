@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    BPS_DENOMINATOR, DEFAULT_MAX_STALENESS_SECS, MIN_DISPUTE_WINDOW_SECS, PARAMETER_TIMELOCK_SECS,
+    SETTLEMENT_CHALLENGE_WINDOW_SECS, SETTLEMENT_GRACE_PERIOD_SECS,
+};
+use crate::errors::ErrorCode;
+use crate::types::{Amount, Price, Quantity, Rounding, RiskParameter, SettlementStage};
+
 #[account]
 #[derive(Default)]
 pub struct ContractState {
@@ -18,15 +25,18 @@ pub struct ContractState {
     pub lcontract_mint: Pubkey,   
     pub scontract_mint: Pubkey,     
     pub oracle_feed_type: u8,
+    /// Oracle account, or the Pyth feed id for `FeedType::PythPull`
     pub oracle_feed_key: Pubkey,   
+    /// `10^vayoo_precisions`, kept for off-chain readers
     pub oracle_price_multiplier: u64,
 
-    pub limiting_amplitude: u64, 
-    pub starting_price: u64,   
+    pub limiting_amplitude: Price,
+    pub starting_price: Price,
     pub starting_time: u64,      
-    pub ending_price: u64,       
+    pub ending_price: Price,
     pub ending_time: u64,
 
+    /// Maximum `global_current_issued_lcontract` (0: uncapped)
     pub cap_product: u64,
     pub current_tvl_usdc: u64,
     pub current_tvl_underlying: u64,
@@ -35,7 +45,462 @@ pub struct ContractState {
 
     pub test_mode: u64,
     pub bands_shift: u64, 
+    /// Decimals of every `Price` of this contract
     pub vayoo_precisions:u8 ,
 
-    pub reserved: [u64; 10],
+    /// Widest oracle confidence interval accepted, in bps of the price (0: not enforced)
+    pub max_confidence_bps: u64,
+
+    /// Oldest oracle price accepted when creating the contract, in seconds (0: default)
+    pub max_staleness_init_secs: u64,
+    /// Oldest oracle price accepted when settling the contract, in seconds (0: default)
+    pub max_staleness_settle_secs: u64,
+
+    /// How the ending price is derived, see `SettlementMode`
+    pub settlement_mode: u8,
+    /// Length of the window before maturity in which settlement samples are recorded, in seconds
+    pub settlement_window_secs: u64,
+
+    /// Fallback ending price proposed by the authority when the oracle is unavailable at maturity
+    pub proposed_ending_price: Price,
+    /// When the fallback price was proposed (0: no proposal)
+    pub proposed_at: u64,
+
+    /// When settling mode was entered
+    pub settling_time: u64,
+    /// Time after settling during which the ending price can be re-submitted, in seconds
+    pub dispute_window_secs: u64,
+
+    /// Signers of the settlement attestation, bit `i` for `AttestationSigners::signers[i]`
+    pub attestation_signers_mask: u8,
+
+    /// Emergency mode was enabled, see `EmergencyState`
+    pub is_emergency: bool,
+
+    /// Holds the admin rights of the contract, `authority` only seeds the contract PDA
+    pub admin_authority: Pubkey,
+    /// Admin authority proposed by `admin_authority`, until it accepts (default: none)
+    pub pending_admin_authority: Pubkey,
+
+    /// `RiskParameter` of the change waiting for its timelock
+    pub queued_parameter: u8,
+    pub queued_value: u64,
+    /// When the queued change can be applied (0: nothing queued)
+    pub queued_effective_at: u64,
+
+    /// Maximum `current_tvl_usdc` (0: uncapped)
+    pub max_tvl_usdc: u64,
+
+    pub reserved: [u64; 4],
+}
+
+impl ContractState {
+    /// Collateral worth `quantity` contracts at `price`
+    pub fn collateral_for(
+        &self,
+        price: Price,
+        quantity: Quantity,
+        rounding: Rounding,
+    ) -> Result<Amount> {
+        price.collateral_for(quantity, self.vayoo_precisions, rounding)
+    }
+
+    /// Collateral locked for `quantity` contracts, the full width of the band per contract
+    pub fn locked_collateral(&self, quantity: Quantity, rounding: Rounding) -> Result<Amount> {
+        self.collateral_for(self.limiting_amplitude, quantity, rounding)
+    }
+
+    /// Staleness window for the settlement price, contracts created before
+    /// it was configurable keep the historical default
+    pub fn settle_staleness(&self) -> u64 {
+        if self.max_staleness_settle_secs == 0 {
+            DEFAULT_MAX_STALENESS_SECS
+        } else {
+            self.max_staleness_settle_secs
+        }
+    }
+
+    /// The oracle had the grace period following maturity to settle the contract,
+    /// a fallback price can be proposed
+    pub fn grace_period_over(&self, time_now: u64) -> bool {
+        time_now
+            >= self
+                .ending_time
+                .saturating_add(SETTLEMENT_GRACE_PERIOD_SECS)
+    }
+
+    /// Proposed fallback price, once its challenge window is over
+    pub fn finalizable_proposal(&self, time_now: u64) -> Result<Price> {
+        let challenge_end = self
+            .proposal_challenge_end()
+            .ok_or(ErrorCode::NoSettlementProposal)?;
+        require!(time_now >= challenge_end, ErrorCode::ChallengeWindowNotOver);
+        Ok(self.proposed_ending_price)
+    }
+
+    /// When the proposed fallback price can be finalized, if one was proposed
+    pub fn proposal_challenge_end(&self) -> Option<u64> {
+        if self.proposed_at == 0 {
+            return None;
+        }
+        Some(self.proposed_at + SETTLEMENT_CHALLENGE_WINDOW_SECS)
+    }
+
+    /// Fixes the ending price and stops deposits and trading
+    pub fn enter_settlement(&mut self, ending_price: Price, time_now: u64) {
+        self.ending_price = ending_price;
+        self.settling_time = time_now;
+        self.is_settling = true;
+        self.is_halted_deposit = true;
+        self.is_halted_trading = true;
+    }
+
+    /// Payouts are unlocked once the dispute window following settling mode is over,
+    /// contracts created before it existed have none
+    pub fn settlement_stage(&self, time_now: u64) -> SettlementStage {
+        if !self.is_settling {
+            SettlementStage::Active
+        } else if time_now < self.settling_time.saturating_add(self.dispute_window_secs) {
+            SettlementStage::Settling
+        } else {
+            SettlementStage::Finalized
+        }
+    }
+
+    /// Timestamp from which settlement samples are recorded
+    pub fn settlement_window_start(&self) -> i64 {
+        self.ending_time.saturating_sub(self.settlement_window_secs) as i64
+    }
+
+    /// Fails if the issued lcontracts go over the open interest cap
+    pub fn check_open_interest_cap(&self) -> Result<()> {
+        require!(
+            self.cap_product == 0 || self.global_current_issued_lcontract <= self.cap_product,
+            ErrorCode::OpenInterestCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Fails if deposits went over the TVL cap of the contract
+    pub fn check_tvl_cap(&self) -> Result<()> {
+        require!(
+            self.max_tvl_usdc == 0 || self.current_tvl_usdc <= self.max_tvl_usdc,
+            ErrorCode::ContractTvlCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Counts a deposit in the contract TVL, fails if it goes over the cap
+    pub fn add_tvl(&mut self, amount: u64) -> Result<()> {
+        self.current_tvl_usdc = self
+            .current_tvl_usdc
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.check_tvl_cap()
+    }
+
+    /// Removes withdrawn deposits from the contract TVL
+    pub fn remove_tvl(&mut self, amount: u64) -> Result<()> {
+        self.current_tvl_usdc = self
+            .current_tvl_usdc
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Positions are open, risk parameters only change through the timelock
+    pub fn is_live(&self) -> bool {
+        self.global_current_issued_lcontract > 0
+    }
+
+    /// Fails if `parameter` cannot be set to `value` now.
+    /// Positions were collateralized at the current amplitude, so it is fixed once any is open.
+    pub fn check_parameter(&self, parameter: RiskParameter, value: u64) -> Result<()> {
+        match parameter {
+            RiskParameter::LimitingAmplitude => {
+                require!(!self.is_live(), ErrorCode::AmplitudeLocked);
+                require!(value > 0, ErrorCode::InvalidArgument);
+            }
+            RiskParameter::MaxConfidenceBps => {
+                require!(
+                    value > 0 && value <= BPS_DENOMINATOR,
+                    ErrorCode::InvalidArgument
+                );
+            }
+            RiskParameter::MaxStalenessSettleSecs | RiskParameter::MaxStalenessInitSecs => {
+                require!(value > 0, ErrorCode::InvalidArgument);
+            }
+            RiskParameter::DisputeWindowSecs => {
+                require!(
+                    cfg!(feature = "dev") || value >= MIN_DISPUTE_WINDOW_SECS,
+                    ErrorCode::InvalidArgument
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_parameter(&mut self, parameter: RiskParameter, value: u64) -> Result<()> {
+        self.check_parameter(parameter, value)?;
+        match parameter {
+            RiskParameter::LimitingAmplitude => self.limiting_amplitude = Price::new(value),
+            RiskParameter::MaxConfidenceBps => self.max_confidence_bps = value,
+            RiskParameter::MaxStalenessSettleSecs => self.max_staleness_settle_secs = value,
+            RiskParameter::DisputeWindowSecs => self.dispute_window_secs = value,
+            RiskParameter::MaxStalenessInitSecs => self.max_staleness_init_secs = value,
+        }
+        Ok(())
+    }
+
+    /// Sets `parameter` without the timelock, only until positions are open
+    pub fn set_parameter_now(&mut self, parameter: RiskParameter, value: u64) -> Result<()> {
+        require!(!self.is_live(), ErrorCode::ParameterTimelocked);
+        self.set_parameter(parameter, value)
+    }
+
+    /// Queues a change of `parameter`, replacing any change still waiting.
+    /// Returns when it can be applied
+    pub fn queue_parameter(
+        &mut self,
+        parameter: RiskParameter,
+        value: u64,
+        time_now: u64,
+    ) -> Result<u64> {
+        self.check_parameter(parameter, value)?;
+        self.queued_parameter = parameter as u8;
+        self.queued_value = value;
+        self.queued_effective_at = time_now + PARAMETER_TIMELOCK_SECS;
+        Ok(self.queued_effective_at)
+    }
+
+    pub fn cancel_queued_parameter(&mut self) -> Result<()> {
+        require!(self.queued_effective_at != 0, ErrorCode::NoQueuedParameter);
+        self.queued_parameter = 0;
+        self.queued_value = 0;
+        self.queued_effective_at = 0;
+        Ok(())
+    }
+
+    /// Applies the queued change once its timelock is over, returns the parameter and value set.
+    /// Checked again, lcontracts may have been issued since the change was queued
+    pub fn apply_queued_parameter(&mut self, time_now: u64) -> Result<(RiskParameter, u64)> {
+        require!(self.queued_effective_at != 0, ErrorCode::NoQueuedParameter);
+        require!(
+            time_now >= self.queued_effective_at,
+            ErrorCode::TimelockNotOver
+        );
+        let parameter = RiskParameter::try_from(self.queued_parameter)?;
+        let value = self.queued_value;
+        self.set_parameter(parameter, value)?;
+        self.cancel_queued_parameter()?;
+        Ok((parameter, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proposals_wait_for_the_grace_period() {
+        let contract_state = ContractState {
+            ending_time: 1_000,
+            ..Default::default()
+        };
+        assert!(!contract_state.grace_period_over(1_000));
+        assert!(!contract_state.grace_period_over(1_000 + SETTLEMENT_GRACE_PERIOD_SECS - 1));
+        assert!(contract_state.grace_period_over(1_000 + SETTLEMENT_GRACE_PERIOD_SECS));
+    }
+
+    #[test]
+    fn finalizes_proposals_after_the_challenge_window() {
+        let mut contract_state = ContractState::default();
+        assert!(contract_state.finalizable_proposal(u64::MAX).is_err());
+
+        contract_state.proposed_ending_price = Price::new(42);
+        contract_state.proposed_at = 5_000;
+        let challenge_end = 5_000 + SETTLEMENT_CHALLENGE_WINDOW_SECS;
+        assert_eq!(contract_state.proposal_challenge_end(), Some(challenge_end));
+        assert!(contract_state
+            .finalizable_proposal(challenge_end - 1)
+            .is_err());
+        assert_eq!(
+            contract_state.finalizable_proposal(challenge_end).unwrap(),
+            Price::new(42)
+        );
+    }
+
+    #[test]
+    fn finalizes_after_the_dispute_window() {
+        let mut contract_state = ContractState {
+            dispute_window_secs: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            contract_state.settlement_stage(1_000),
+            SettlementStage::Active
+        );
+
+        contract_state.enter_settlement(Price::new(42), 1_000);
+        assert_eq!(
+            contract_state.settlement_stage(1_099),
+            SettlementStage::Settling
+        );
+        assert_eq!(
+            contract_state.settlement_stage(1_100),
+            SettlementStage::Finalized
+        );
+
+        contract_state.dispute_window_secs = 0;
+        assert_eq!(
+            contract_state.settlement_stage(1_000),
+            SettlementStage::Finalized
+        );
+    }
+
+    #[test]
+    fn caps_open_interest() {
+        let mut contract_state = ContractState {
+            global_current_issued_lcontract: 1_000,
+            ..Default::default()
+        };
+        assert!(contract_state.check_open_interest_cap().is_ok());
+
+        contract_state.cap_product = 1_000;
+        assert!(contract_state.check_open_interest_cap().is_ok());
+        contract_state.global_current_issued_lcontract += 1;
+        assert!(contract_state.check_open_interest_cap().is_err());
+    }
+
+    #[test]
+    fn caps_tvl() {
+        let mut contract_state = ContractState {
+            current_tvl_usdc: 5_000_000,
+            ..Default::default()
+        };
+        assert!(contract_state.check_tvl_cap().is_ok());
+
+        contract_state.max_tvl_usdc = 5_000_000;
+        assert!(contract_state.check_tvl_cap().is_ok());
+        contract_state.current_tvl_usdc += 1;
+        assert!(contract_state.check_tvl_cap().is_err());
+    }
+
+    #[test]
+    fn tracks_tvl_through_deposits_and_withdrawals() {
+        let mut contract_state = ContractState {
+            max_tvl_usdc: 1_000,
+            ..Default::default()
+        };
+        contract_state.add_tvl(600).unwrap();
+        assert_eq!(
+            contract_state.add_tvl(401).unwrap_err(),
+            ErrorCode::ContractTvlCapExceeded.into()
+        );
+
+        contract_state.current_tvl_usdc = 600;
+        contract_state.remove_tvl(200).unwrap();
+        assert_eq!(contract_state.current_tvl_usdc, 400);
+        contract_state.add_tvl(600).unwrap();
+        assert_eq!(contract_state.current_tvl_usdc, 1_000);
+
+        assert_eq!(
+            contract_state.remove_tvl(1_001).unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+        assert_eq!(contract_state.current_tvl_usdc, 1_000);
+    }
+
+    #[test]
+    fn locks_amplitude_once_lcontracts_are_issued() {
+        let mut contract_state = ContractState {
+            limiting_amplitude: Price::new(100),
+            ..Default::default()
+        };
+        contract_state
+            .set_parameter(RiskParameter::LimitingAmplitude, 200)
+            .unwrap();
+        assert_eq!(contract_state.limiting_amplitude, Price::new(200));
+
+        contract_state.global_current_issued_lcontract = 1;
+        assert!(contract_state
+            .set_parameter(RiskParameter::LimitingAmplitude, 300)
+            .is_err());
+        assert_eq!(contract_state.limiting_amplitude, Price::new(200));
+
+        contract_state
+            .set_parameter(RiskParameter::DisputeWindowSecs, MIN_DISPUTE_WINDOW_SECS)
+            .unwrap();
+        assert_eq!(
+            contract_state
+                .set_parameter(RiskParameter::DisputeWindowSecs, 0)
+                .is_ok(),
+            cfg!(feature = "dev")
+        );
+        assert!(contract_state
+            .set_parameter(RiskParameter::MaxConfidenceBps, BPS_DENOMINATOR + 1)
+            .is_err());
+    }
+
+    #[test]
+    fn applies_queued_parameters_after_the_timelock() {
+        let mut contract_state = ContractState {
+            limiting_amplitude: Price::new(100),
+            dispute_window_secs: 600,
+            ..Default::default()
+        };
+        assert!(contract_state.apply_queued_parameter(u64::MAX).is_err());
+        assert!(contract_state.cancel_queued_parameter().is_err());
+
+        let effective_at = contract_state
+            .queue_parameter(RiskParameter::DisputeWindowSecs, 3_600, 1_000)
+            .unwrap();
+        assert_eq!(effective_at, 1_000 + PARAMETER_TIMELOCK_SECS);
+        assert!(contract_state
+            .apply_queued_parameter(effective_at - 1)
+            .is_err());
+        assert_eq!(contract_state.dispute_window_secs, 600);
+        assert_eq!(
+            contract_state.apply_queued_parameter(effective_at).unwrap(),
+            (RiskParameter::DisputeWindowSecs, 3_600)
+        );
+        assert_eq!(contract_state.dispute_window_secs, 3_600);
+        assert_eq!(contract_state.queued_effective_at, 0);
+
+        // Cancelled changes never apply
+        contract_state
+            .queue_parameter(RiskParameter::MaxStalenessSettleSecs, 120, 2_000)
+            .unwrap();
+        contract_state.cancel_queued_parameter().unwrap();
+        assert!(contract_state.apply_queued_parameter(u64::MAX).is_err());
+        assert_eq!(contract_state.max_staleness_settle_secs, 0);
+
+        // Amplitude changes queued before issuance are rejected once lcontracts are issued
+        let effective_at = contract_state
+            .queue_parameter(RiskParameter::LimitingAmplitude, 200, 3_000)
+            .unwrap();
+        contract_state.global_current_issued_lcontract = 1;
+        assert!(contract_state.apply_queued_parameter(effective_at).is_err());
+        assert_eq!(contract_state.limiting_amplitude, Price::new(100));
+    }
+
+    #[test]
+    fn sets_parameters_directly_until_positions_are_open() {
+        let mut contract_state = ContractState::default();
+        contract_state
+            .set_parameter_now(RiskParameter::MaxStalenessInitSecs, 300)
+            .unwrap();
+        assert_eq!(contract_state.max_staleness_init_secs, 300);
+        assert!(contract_state
+            .set_parameter_now(RiskParameter::MaxStalenessSettleSecs, 0)
+            .is_err());
+
+        contract_state.global_current_issued_lcontract = 1;
+        assert!(contract_state
+            .set_parameter_now(RiskParameter::DisputeWindowSecs, 3_600)
+            .is_err());
+        contract_state
+            .queue_parameter(RiskParameter::DisputeWindowSecs, 3_600, 0)
+            .unwrap();
+    }
 }
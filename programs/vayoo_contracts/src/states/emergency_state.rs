@@ -0,0 +1,100 @@
+// libraries
+use anchor_lang::prelude::*;
+
+/// Emergency mode of a contract, which exists once its admin authority or the guardian enabled it.
+/// Each user can then pull back their collateral, and each lcontract burned is paid
+/// a pro-rata share of the escrow, as of the snapshot taken when the mode was enabled.
+///
+/// Only settlement moves collateral into the escrow. Before it, the locked collateral goes
+/// back in full to the side that locked it and lcontracts are burned for nothing: the mode
+/// unwinds the contract as if its price never moved from the lower bound.
+#[account]
+#[derive(Default)]
+pub struct EmergencyState {
+    pub contract_account: Pubkey,
+    pub bump: u8,
+    pub started_at: i64,
+    /// Contract authority or guardian who enabled the mode
+    pub started_by: Pubkey,
+
+    /// Escrow balance shared between lcontract holders
+    pub escrow_snapshot: u64,
+    /// lcontract supply when the mode was enabled, the denominator of escrow shares
+    pub issued_lcontract_snapshot: u64,
+
+    /// Escrow paid out so far
+    pub escrow_refunded: u64,
+    /// Refund claims so far
+    pub refunds: u64,
+
+    pub reserved: [u64; 8],
+}
+
+impl EmergencyState {
+    /// Escrow owed for `lcontract_burned` lcontracts, rounded down
+    pub fn escrow_share(&self, lcontract_burned: u64) -> u64 {
+        if self.issued_lcontract_snapshot == 0 {
+            return 0;
+        }
+        let share = self.escrow_snapshot as u128 * lcontract_burned as u128
+            / self.issued_lcontract_snapshot as u128;
+        // Bounded by the escrow snapshot as long as lcontract_burned <= supply
+        share.min(self.escrow_snapshot as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_escrow_pro_rata() {
+        let emergency_state = EmergencyState {
+            escrow_snapshot: 1_000,
+            issued_lcontract_snapshot: 3,
+            ..Default::default()
+        };
+        assert_eq!(emergency_state.escrow_share(1), 333);
+        assert_eq!(emergency_state.escrow_share(2), 666);
+        assert_eq!(emergency_state.escrow_share(0), 0);
+        assert_eq!(emergency_state.escrow_share(4), 1_000);
+
+        let nothing_issued = EmergencyState {
+            escrow_snapshot: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(nothing_issued.escrow_share(5), 0);
+    }
+
+    #[test]
+    fn pays_nothing_for_lcontracts_before_settlement() {
+        // Escrow still empty: the lockers get their collateral back, lcontracts nothing
+        let emergency_state = EmergencyState {
+            escrow_snapshot: 0,
+            issued_lcontract_snapshot: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(emergency_state.escrow_share(600), 0);
+        assert_eq!(emergency_state.escrow_share(1_000), 0);
+    }
+
+    #[test]
+    fn shares_escrow_across_a_mixed_book() {
+        // Users bought 600 lcontracts from the pool, the MM still holds 300 of its mints
+        // and the pool 100, all of them claimable once burned
+        let (user_longs, mm_held, pool_held) = (600, 300, 100);
+        let emergency_state = EmergencyState {
+            escrow_snapshot: 10_007,
+            issued_lcontract_snapshot: user_longs + mm_held + pool_held,
+            ..Default::default()
+        };
+        let shares =
+            [user_longs, mm_held, pool_held].map(|burned| emergency_state.escrow_share(burned));
+        assert_eq!(shares, [6_004, 3_002, 1_000]);
+
+        // Nothing stranded but the rounding, at most one unit per claim
+        let paid: u64 = shares.iter().sum();
+        assert!(paid <= emergency_state.escrow_snapshot);
+        assert!(emergency_state.escrow_snapshot - paid < shares.len() as u64);
+    }
+}
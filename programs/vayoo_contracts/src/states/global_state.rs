@@ -1,6 +1,8 @@
 // libraries
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
 #[account]
 #[derive(Default)]
 pub struct GlobalState {
@@ -10,8 +12,81 @@ pub struct GlobalState {
     /// Is contract paused
     pub paused: bool,
 
+    /// Collateral deposited in all contracts
     pub total_tvl_usdc: u64,
 
+    /// Can re-submit the settlement price of any contract during its dispute window
+    pub guardian: Pubkey,
+
+    /// `PausableInstruction` bits still allowed while paused
+    pub pause_allowlist: u64,
+
+    /// Authority proposed by `authority`, until it accepts (default: none)
+    pub pending_authority: Pubkey,
+
+    /// Maximum `total_tvl_usdc` (0: uncapped)
+    pub max_total_tvl_usdc: u64,
+
     /// extra space
-    pub reserved: [u64; 15],
+    pub reserved: [u64; 5],
+}
+
+impl GlobalState {
+    /// Fails if deposits went over the TVL cap of the protocol
+    pub fn check_total_tvl_cap(&self) -> Result<()> {
+        require!(
+            self.max_total_tvl_usdc == 0 || self.total_tvl_usdc <= self.max_total_tvl_usdc,
+            ErrorCode::ProtocolTvlCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Counts a deposit in the protocol TVL, fails if it goes over the cap
+    pub fn add_tvl(&mut self, amount: u64) -> Result<()> {
+        self.total_tvl_usdc = self
+            .total_tvl_usdc
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.check_total_tvl_cap()
+    }
+
+    /// Removes withdrawn deposits from the protocol TVL
+    pub fn remove_tvl(&mut self, amount: u64) -> Result<()> {
+        self.total_tvl_usdc = self
+            .total_tvl_usdc
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_the_protocol_tvl() {
+        let mut global_state = GlobalState {
+            max_total_tvl_usdc: 10_000,
+            ..Default::default()
+        };
+        global_state.add_tvl(9_000).unwrap();
+        global_state.remove_tvl(1_000).unwrap();
+        assert_eq!(global_state.total_tvl_usdc, 8_000);
+        global_state.add_tvl(2_000).unwrap();
+        assert_eq!(
+            global_state.add_tvl(1).unwrap_err(),
+            ErrorCode::ProtocolTvlCapExceeded.into()
+        );
+
+        global_state.max_total_tvl_usdc = 0;
+        global_state.total_tvl_usdc = 10_000;
+        global_state.add_tvl(1_000_000).unwrap();
+        assert_eq!(
+            global_state.remove_tvl(u64::MAX).unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+        global_state.remove_tvl(1_010_000).unwrap();
+        assert_eq!(global_state.total_tvl_usdc, 0);
+    }
 }
@@ -0,0 +1,19 @@
+// libraries
+use anchor_lang::prelude::*;
+
+/// Price feed owned by the program, for localnet and offline tests.
+/// Only writable when built with the `dev` feature.
+#[account]
+#[derive(Default)]
+pub struct ManualFeed {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub name: String,
+
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+
+    pub reserved: [u64; 4],
+}
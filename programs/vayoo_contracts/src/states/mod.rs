@@ -1,11 +1,41 @@
 pub mod global_state;
 pub use global_state::*;
 
+pub mod roles;
+pub use roles::*;
+
 pub mod contract_state;
 pub use contract_state::*;
 
 pub mod user_state;
 pub use user_state::*;
 
+pub mod emergency_state;
+pub use emergency_state::*;
+
+pub mod settlement_samples;
+pub use settlement_samples::*;
+
+pub mod aggregate_feed;
+pub use aggregate_feed::*;
+
+pub mod ratio_feed;
+pub use ratio_feed::*;
+
+pub mod basket_feed;
+pub use basket_feed::*;
+
+pub mod attestation_signers;
+pub use attestation_signers::*;
+
+pub mod manual_feed;
+pub use manual_feed::*;
+
 pub mod price_feed;
 pub use price_feed::*;
+
+pub mod price_update;
+pub use price_update::*;
+
+pub mod pull_feed;
+pub use pull_feed::*;
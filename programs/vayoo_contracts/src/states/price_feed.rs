@@ -3,6 +3,7 @@ use std::{ops::Deref, str::FromStr};
 use anchor_lang::prelude::*;
 use pyth_sdk_solana::state::load_price_account;
 
+use crate::constants::PYTH_PROGRAM_ID;
 use crate::errors::ErrorCode;
 
 #[derive(Clone)]
@@ -10,9 +11,8 @@ pub struct PriceFeed(pyth_sdk::PriceFeed);
 
 impl anchor_lang::Owner for PriceFeed {
     fn owner() -> Pubkey {
-        // Make sure the owner is the pyth oracle account on solana mainnet-beta
-        let oracle_addr = "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH";
-        Pubkey::from_str(oracle_addr).unwrap()
+        // Make sure the owner is the pyth oracle program of the cluster we are built for
+        Pubkey::from_str(PYTH_PROGRAM_ID).unwrap()
     }
 }
 
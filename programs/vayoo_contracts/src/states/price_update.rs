@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+
+use crate::constants::PYTH_RECEIVER_PROGRAM_ID;
+use crate::errors::ErrorCode;
+
+/// How many Wormhole guardian signatures were checked when the update was posted
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Pull oracle price account posted by the Pyth receiver program,
+/// mirrors `PriceUpdateV2` of pyth-solana-receiver-sdk
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+impl PriceUpdateV2 {
+    /// Anchor account discriminator, first 8 bytes of sha256("account:PriceUpdateV2")
+    pub const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+    /// Pyth feed id, as stored in `ContractState::oracle_feed_key`
+    pub fn feed_key(&self) -> Pubkey {
+        Pubkey::new_from_array(self.price_message.feed_id)
+    }
+}
+
+impl anchor_lang::Owner for PriceUpdateV2 {
+    fn owner() -> Pubkey {
+        // Same receiver program on every cluster
+        Pubkey::from_str(PYTH_RECEIVER_PROGRAM_ID).unwrap()
+    }
+}
+
+impl anchor_lang::AccountDeserialize for PriceUpdateV2 {
+    fn try_deserialize(data: &mut &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == Self::DISCRIMINATOR,
+            ErrorCode::InvalidOraclefeed
+        );
+        Self::try_deserialize_unchecked(data)
+    }
+
+    fn try_deserialize_unchecked(data: &mut &[u8]) -> Result<Self> {
+        let mut data: &[u8] = &data[8..];
+        AnchorDeserialize::deserialize(&mut data).map_err(|_| error!(ErrorCode::InvalidOraclefeed))
+    }
+}
+
+impl anchor_lang::AccountSerialize for PriceUpdateV2 {
+    fn try_serialize<W: std::io::Write>(&self, _writer: &mut W) -> std::result::Result<(), Error> {
+        Err(error!(ErrorCode::TryToSerializePriceAccount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn discriminator_matches_the_receiver_account() {
+        let expected = hash(b"account:PriceUpdateV2").to_bytes();
+        assert_eq!(PriceUpdateV2::DISCRIMINATOR, expected[..8]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+
+use crate::constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+use crate::errors::ErrorCode;
+
+/// Switchboard On-Demand pull feed, reading only the fields settlement needs from
+/// the zero-copy `PullFeedAccountData` layout of switchboard-on-demand
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PullFeed {
+    pub feed_hash: [u8; 32],
+    pub last_update_timestamp: i64,
+    /// Latest result, with `PullFeed::DECIMALS` decimals
+    pub value: i128,
+    pub std_dev: i128,
+    pub num_samples: u8,
+}
+
+impl PullFeed {
+    /// Anchor account discriminator, first 8 bytes of sha256("account:PullFeedAccountData")
+    pub const DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+    /// Decimals of every On-Demand result
+    pub const DECIMALS: u32 = 18;
+
+    // Offsets in the account data, discriminator included
+    // (32 submissions of 64 bytes, then the feed configuration, then the current result)
+    pub const FEED_HASH_OFFSET: usize = 2120;
+    pub const LAST_UPDATE_TIMESTAMP_OFFSET: usize = 2216;
+    pub const RESULT_VALUE_OFFSET: usize = 2264;
+    pub const RESULT_STD_DEV_OFFSET: usize = 2280;
+    pub const RESULT_NUM_SAMPLES_OFFSET: usize = 2360;
+    pub const MIN_LEN: usize = 2392;
+}
+
+impl anchor_lang::Owner for PullFeed {
+    fn owner() -> Pubkey {
+        Pubkey::from_str(SWITCHBOARD_ON_DEMAND_PROGRAM_ID).unwrap()
+    }
+}
+
+impl anchor_lang::AccountDeserialize for PullFeed {
+    fn try_deserialize(data: &mut &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == Self::DISCRIMINATOR,
+            ErrorCode::InvalidSwitchboardAccount
+        );
+        Self::try_deserialize_unchecked(data)
+    }
+
+    fn try_deserialize_unchecked(data: &mut &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= Self::MIN_LEN,
+            ErrorCode::InvalidSwitchboardAccount
+        );
+        let bytes = |offset: usize, len: usize| &data[offset..offset + len];
+        Ok(PullFeed {
+            feed_hash: bytes(Self::FEED_HASH_OFFSET, 32).try_into().unwrap(),
+            last_update_timestamp: i64::from_le_bytes(
+                bytes(Self::LAST_UPDATE_TIMESTAMP_OFFSET, 8)
+                    .try_into()
+                    .unwrap(),
+            ),
+            value: i128::from_le_bytes(bytes(Self::RESULT_VALUE_OFFSET, 16).try_into().unwrap()),
+            std_dev: i128::from_le_bytes(
+                bytes(Self::RESULT_STD_DEV_OFFSET, 16).try_into().unwrap(),
+            ),
+            num_samples: data[Self::RESULT_NUM_SAMPLES_OFFSET],
+        })
+    }
+}
+
+impl anchor_lang::AccountSerialize for PullFeed {
+    fn try_serialize<W: std::io::Write>(&self, _writer: &mut W) -> std::result::Result<(), Error> {
+        Err(error!(ErrorCode::TryToSerializePriceAccount))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::AccountDeserialize;
+
+    /// Account data of an On-Demand feed with the given latest result
+    pub fn fixture(
+        value: i128,
+        std_dev: i128,
+        num_samples: u8,
+        last_update_timestamp: i64,
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; PullFeed::MIN_LEN];
+        data[..8].copy_from_slice(&PullFeed::DISCRIMINATOR);
+        data[PullFeed::FEED_HASH_OFFSET..][..32].copy_from_slice(&[9; 32]);
+        data[PullFeed::LAST_UPDATE_TIMESTAMP_OFFSET..][..8]
+            .copy_from_slice(&last_update_timestamp.to_le_bytes());
+        data[PullFeed::RESULT_VALUE_OFFSET..][..16].copy_from_slice(&value.to_le_bytes());
+        data[PullFeed::RESULT_STD_DEV_OFFSET..][..16].copy_from_slice(&std_dev.to_le_bytes());
+        data[PullFeed::RESULT_NUM_SAMPLES_OFFSET] = num_samples;
+        data
+    }
+
+    #[test]
+    fn discriminator_matches_the_on_demand_account() {
+        let expected = hash(b"account:PullFeedAccountData").to_bytes();
+        assert_eq!(PullFeed::DISCRIMINATOR, expected[..8]);
+    }
+
+    #[test]
+    fn parses_crafted_accounts() {
+        let data = fixture(-5, 7, 3, 1_700_000_000);
+        let feed = PullFeed::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(feed.feed_hash, [9; 32]);
+        assert_eq!(feed.last_update_timestamp, 1_700_000_000);
+        assert_eq!((feed.value, feed.std_dev, feed.num_samples), (-5, 7, 3));
+
+        let mut wrong_discriminator = data.clone();
+        wrong_discriminator[0] ^= 1;
+        assert!(PullFeed::try_deserialize(&mut &wrong_discriminator[..]).is_err());
+        assert!(PullFeed::try_deserialize(&mut &data[..PullFeed::MIN_LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::states::AggregateSource;
+
+/// Pair of oracle feeds a contract with `FeedType::Ratio` starts and settles on,
+/// the price of `base` expressed in units of `quote` (e.g. ETH/USD over BTC/USD for ETH/BTC)
+#[account]
+#[derive(Default)]
+pub struct RatioFeed {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub name: String,
+    pub base: AggregateSource,
+    pub quote: AggregateSource,
+
+    pub reserved: [u64; 8],
+}
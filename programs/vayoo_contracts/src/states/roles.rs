@@ -0,0 +1,54 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::types::Role;
+
+/// Keys holding each `Role`, next to the `GlobalState` (default key: role not granted)
+#[account]
+#[derive(Default)]
+pub struct Roles {
+    pub bump: u8,
+    pub pauser: Pubkey,
+    pub settler: Pubkey,
+    pub parameter_admin: Pubkey,
+
+    pub reserved: [u64; 8],
+}
+
+impl Roles {
+    pub fn holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Pauser => self.pauser,
+            Role::Settler => self.settler,
+            Role::ParameterAdmin => self.parameter_admin,
+        }
+    }
+
+    /// Whether `key` is `admin`, which keeps every right, or holds `role`
+    pub fn grants(&self, role: Role, admin: &Pubkey, key: &Pubkey) -> bool {
+        key == admin || (*key != Pubkey::default() && self.holder(role) == *key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_each_role_to_its_holder_only() {
+        let admin = Pubkey::new_from_array([1; 32]);
+        let keeper = Pubkey::new_from_array([2; 32]);
+        let roles = Roles {
+            settler: keeper,
+            ..Default::default()
+        };
+
+        assert!(roles.grants(Role::Settler, &admin, &keeper));
+        assert!(!roles.grants(Role::Pauser, &admin, &keeper));
+        assert!(!roles.grants(Role::ParameterAdmin, &admin, &keeper));
+        for role in [Role::Pauser, Role::Settler, Role::ParameterAdmin] {
+            assert!(roles.grants(role, &admin, &admin));
+            assert!(!roles.grants(role, &admin, &Pubkey::default()));
+        }
+    }
+}
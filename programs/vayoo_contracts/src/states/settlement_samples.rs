@@ -0,0 +1,165 @@
+// libraries
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_SETTLEMENT_SAMPLES;
+use crate::types::Price;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SettlementSample {
+    /// Oracle price, with the contract `vayoo_precisions` decimals
+    pub price: Price,
+    /// Oracle publish time of the price
+    pub timestamp: i64,
+}
+
+/// Ring buffer of oracle prices recorded during the settlement window of a contract
+#[account]
+#[derive(Default)]
+pub struct SettlementSamples {
+    pub contract_state: Pubkey,
+    pub bump: u8,
+    /// Number of samples ever recorded, the next one is written at `count % MAX_SETTLEMENT_SAMPLES`
+    pub count: u64,
+    pub samples: [SettlementSample; MAX_SETTLEMENT_SAMPLES],
+}
+
+impl SettlementSamples {
+    pub fn last(&self) -> Option<SettlementSample> {
+        if self.count == 0 {
+            return None;
+        }
+        let index = (self.count - 1) as usize % MAX_SETTLEMENT_SAMPLES;
+        Some(self.samples[index])
+    }
+
+    /// Samples must be at least `settlement_window_secs / MAX_SETTLEMENT_SAMPLES` apart,
+    /// so the buffer spans the whole window and cannot be filled right before maturity
+    pub fn min_spacing(settlement_window_secs: u64) -> i64 {
+        (settlement_window_secs / MAX_SETTLEMENT_SAMPLES as u64) as i64
+    }
+
+    /// Whether a price published at `timestamp` is far enough from the last sample
+    pub fn accepts(&self, timestamp: i64, min_spacing: i64) -> bool {
+        match self.last() {
+            Some(last) => timestamp > last.timestamp && timestamp - last.timestamp >= min_spacing,
+            None => true,
+        }
+    }
+
+    /// Records a sample, overwriting the oldest one once the buffer is full
+    pub fn push(&mut self, sample: SettlementSample) {
+        let index = self.count as usize % MAX_SETTLEMENT_SAMPLES;
+        self.samples[index] = sample;
+        self.count += 1;
+    }
+
+    /// Samples still in the buffer published at or after `since`, oldest first
+    pub fn since(&self, since: i64) -> Vec<SettlementSample> {
+        let kept = (self.count as usize).min(MAX_SETTLEMENT_SAMPLES);
+        let oldest = self.count as usize - kept;
+        (oldest..self.count as usize)
+            .map(|i| self.samples[i % MAX_SETTLEMENT_SAMPLES])
+            .filter(|sample| sample.timestamp >= since)
+            .collect()
+    }
+}
+
+/// Average of the sample prices, each one weighted by how long it stood before
+/// the next sample, the last one standing until `end`
+pub fn time_weighted_average(samples: &[SettlementSample], end: i64) -> Option<Price> {
+    let last = samples.last()?;
+    let mut weighted_sum: u128 = 0;
+    let mut total_weight: u128 = 0;
+    for (i, sample) in samples.iter().enumerate() {
+        let until = samples.get(i + 1).map_or(end, |next| next.timestamp);
+        let weight = until.saturating_sub(sample.timestamp).max(0) as u128;
+        weighted_sum += sample.price.raw() as u128 * weight;
+        total_weight += weight;
+    }
+    if total_weight == 0 {
+        return Some(last.price);
+    }
+    Some(Price::new((weighted_sum / total_weight) as u64))
+}
+
+/// Median of the sample prices, the two middle prices are averaged (rounding down)
+/// when there is an even number of samples
+pub fn median(samples: &[SettlementSample]) -> Option<Price> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut prices: Vec<u64> = samples.iter().map(|sample| sample.price.raw()).collect();
+    prices.sort_unstable();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(Price::new(prices[middle]))
+    } else {
+        let sum = prices[middle - 1] as u128 + prices[middle] as u128;
+        Some(Price::new((sum / 2) as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(price: u64, timestamp: i64) -> SettlementSample {
+        SettlementSample {
+            price: Price::new(price),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn keeps_the_latest_samples_once_full() {
+        let mut buffer = SettlementSamples::default();
+        assert_eq!(buffer.last(), None);
+        let total = MAX_SETTLEMENT_SAMPLES as i64 + 5;
+        for t in 0..total {
+            buffer.push(sample(t as u64, t));
+        }
+        let kept = buffer.since(i64::MIN);
+        assert_eq!(kept.len(), MAX_SETTLEMENT_SAMPLES);
+        assert_eq!(kept[0].timestamp, 5);
+        assert_eq!(buffer.last(), Some(sample(total as u64 - 1, total - 1)));
+        assert_eq!(buffer.since(total - 2).len(), 2);
+    }
+
+    #[test]
+    fn rejects_back_to_back_samples() {
+        let spacing = SettlementSamples::min_spacing(3_200);
+        assert_eq!(spacing, 100);
+
+        let mut buffer = SettlementSamples::default();
+        assert!(buffer.accepts(1_000, spacing));
+        buffer.push(sample(42, 1_000));
+        assert!(!buffer.accepts(1_000, spacing));
+        assert!(!buffer.accepts(1_001, spacing));
+        assert!(!buffer.accepts(1_099, spacing));
+        assert!(buffer.accepts(1_100, spacing));
+
+        // Without a window, each oracle update is still recorded once
+        assert!(!buffer.accepts(1_000, SettlementSamples::min_spacing(0)));
+        assert!(buffer.accepts(1_001, SettlementSamples::min_spacing(0)));
+    }
+
+    #[test]
+    fn weights_prices_by_time() {
+        let samples = [sample(100, 0), sample(200, 30), sample(400, 40)];
+        // 100 * 30 + 200 * 10 + 400 * 20 over 60 seconds
+        assert_eq!(time_weighted_average(&samples, 60), Some(Price::new(216)));
+        assert_eq!(
+            time_weighted_average(&[sample(7, 60)], 60),
+            Some(Price::new(7))
+        );
+        assert_eq!(time_weighted_average(&[], 60), None);
+    }
+
+    #[test]
+    fn ignores_a_single_outlier_in_the_median() {
+        let samples = [sample(100, 0), sample(10_000, 1), sample(101, 2)];
+        assert_eq!(median(&samples), Some(Price::new(101)));
+        assert_eq!(median(&samples[..2]), Some(Price::new(5_050)));
+        assert_eq!(median(&[]), None);
+    }
+}
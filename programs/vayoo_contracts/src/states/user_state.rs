@@ -25,5 +25,10 @@ pub struct UserState {
     pub usdc_withdrawn: u64,
     pub usdc_free: u64,
 
-    pub reserved: [u64; 14],
+    /// Collateral refunded by `emergency_withdraw`, escrow share included
+    pub emergency_refunded_usdc: u64,
+    /// When the last emergency refund was claimed (0: not claimed)
+    pub emergency_refunded_at: u64,
+
+    pub reserved: [u64; 12],
 }
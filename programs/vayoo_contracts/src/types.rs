@@ -1,9 +1,330 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
 #[derive(Default, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
 pub enum FeedType {
     #[default]
     Pyth = 0,
     Switchboard = 1,
-    Unknown = 2,
+    /// Median of up to MAX_AGGREGATE_SOURCES Pyth / Switchboard feeds, see `AggregateFeed`
+    Aggregate = 2,
+    /// Price set by hand in a `ManualFeed`, only writable in dev builds
+    Manual = 3,
+    /// Pyth receiver `PriceUpdateV2` account, matched on its feed id
+    PythPull = 4,
+    /// Switchboard On-Demand pull feed account
+    SwitchboardOnDemand = 5,
+    /// Price signed off-chain by a threshold of `AttestationSigners`, verified with ed25519 instructions
+    SignedAttestation = 6,
+    /// Price of one feed in terms of another, see `RatioFeed`
+    Ratio = 7,
+    /// Weighted sum of several feeds, see `BasketFeed`
+    Basket = 8,
+    Unknown = 9,
+}
+
+impl TryFrom<u8> for FeedType {
+    type Error = Error;
+
+    fn try_from(feed_type: u8) -> Result<Self> {
+        match feed_type {
+            0 => Ok(FeedType::Pyth),
+            1 => Ok(FeedType::Switchboard),
+            2 => Ok(FeedType::Aggregate),
+            3 => Ok(FeedType::Manual),
+            4 => Ok(FeedType::PythPull),
+            5 => Ok(FeedType::SwitchboardOnDemand),
+            6 => Ok(FeedType::SignedAttestation),
+            7 => Ok(FeedType::Ratio),
+            8 => Ok(FeedType::Basket),
+            _ => err!(ErrorCode::InvalidFeedType),
+        }
+    }
+}
+
+/// How the ending price of a contract is derived from its oracle
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SettlementMode {
+    /// Single oracle price read once maturity is reached
+    #[default]
+    Spot = 0,
+    /// Time-weighted average of the samples recorded during the settlement window
+    Twap = 1,
+    /// Median of the samples recorded during the settlement window
+    Median = 2,
+}
+
+impl TryFrom<u8> for SettlementMode {
+    type Error = Error;
+
+    fn try_from(settlement_mode: u8) -> Result<Self> {
+        match settlement_mode {
+            0 => Ok(SettlementMode::Spot),
+            1 => Ok(SettlementMode::Twap),
+            2 => Ok(SettlementMode::Median),
+            _ => err!(ErrorCode::InvalidSettlementMode),
+        }
+    }
+}
+
+/// Halt flag of a contract an admin can set or clear
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HaltFlag {
+    /// `is_halted`, stops deposits and trading
+    #[default]
+    All = 0,
+    /// `is_halted_deposit`, stops deposits and trading
+    Deposit = 1,
+    /// `is_halted_trading`, stops trading and market maker mints and burns
+    Trading = 2,
+}
+
+impl TryFrom<u8> for HaltFlag {
+    type Error = Error;
+
+    fn try_from(halt_flag: u8) -> Result<Self> {
+        match halt_flag {
+            0 => Ok(HaltFlag::All),
+            1 => Ok(HaltFlag::Deposit),
+            2 => Ok(HaltFlag::Trading),
+            _ => err!(ErrorCode::InvalidArgument),
+        }
+    }
+}
+
+/// User facing instructions and settlement cranks the global pause applies to, in
+/// `GlobalState::pause_allowlist` order
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PausableInstruction {
+    InitializeUser = 0,
+    DepositCollateral = 1,
+    WithdrawCollateral = 2,
+    MintLContractMm = 3,
+    BurnLContractMm = 4,
+    LongUser = 5,
+    CloseLongUser = 6,
+    ShortUser = 7,
+    CloseShortUser = 8,
+    UserSettleLong = 9,
+    MmSettleLong = 10,
+    TriggerSettleMode = 11,
+    RecordSettlementSample = 12,
+    FinalizeSettlementProposal = 13,
+}
+
+impl PausableInstruction {
+    pub fn bit(self) -> u64 {
+        1 << self as u64
+    }
+}
+
+/// Admin rights the global authority can grant to a dedicated key, see `Roles`
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Role {
+    /// Halts contracts, pauses the protocol and enables emergency mode
+    Pauser = 0,
+    /// Runs the settlement cranks, prices are only proposed by the contract admin authority
+    Settler = 1,
+    /// Changes the risk parameters of contracts
+    ParameterAdmin = 2,
+}
+
+impl TryFrom<u8> for Role {
+    type Error = Error;
+
+    fn try_from(role: u8) -> Result<Self> {
+        match role {
+            0 => Ok(Role::Pauser),
+            1 => Ok(Role::Settler),
+            2 => Ok(Role::ParameterAdmin),
+            _ => err!(ErrorCode::InvalidArgument),
+        }
+    }
+}
+
+/// Contract parameter the collateral, oracle checks or settlement of open positions depend on
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RiskParameter {
+    LimitingAmplitude = 0,
+    MaxConfidenceBps = 1,
+    MaxStalenessSettleSecs = 2,
+    DisputeWindowSecs = 3,
+    MaxStalenessInitSecs = 4,
+}
+
+impl TryFrom<u8> for RiskParameter {
+    type Error = Error;
+
+    fn try_from(parameter: u8) -> Result<Self> {
+        match parameter {
+            0 => Ok(RiskParameter::LimitingAmplitude),
+            1 => Ok(RiskParameter::MaxConfidenceBps),
+            2 => Ok(RiskParameter::MaxStalenessSettleSecs),
+            3 => Ok(RiskParameter::DisputeWindowSecs),
+            4 => Ok(RiskParameter::MaxStalenessInitSecs),
+            _ => err!(ErrorCode::InvalidArgument),
+        }
+    }
+}
+
+/// Where a contract is in its lifecycle
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SettlementStage {
+    /// Before settlement, positions can be opened and closed
+    Active,
+    /// Ending price set, but can still be re-submitted during the dispute window
+    Settling,
+    /// Ending price final, payouts are unlocked
+    Finalized,
+}
+
+/// Limits an oracle price must satisfy for a contract to start or settle on it
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OracleConfig {
+    /// Widest confidence interval accepted, in bps of the price
+    pub max_confidence_bps: u64,
+    /// Oldest price accepted when creating the contract, in seconds
+    pub max_staleness_init_secs: u64,
+    /// Oldest price accepted when settling the contract, in seconds
+    pub max_staleness_settle_secs: u64,
+}
+
+/// Which way a fixed-point conversion rounds when it is not exact
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Number of lcontract / scontract, in native units of the contract mints
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Quantity(pub u64);
+
+impl Quantity {
+    pub fn checked_add(self, other: Quantity) -> Result<Quantity> {
+        Ok(Quantity(self.0.checked_add(other.0).ok_or(ErrorCode::Overflow)?))
+    }
+
+    pub fn checked_sub(self, other: Quantity) -> Result<Quantity> {
+        Ok(Quantity(self.0.checked_sub(other.0).ok_or(ErrorCode::Overflow)?))
+    }
+}
+
+/// Amount of collateral, in native units of the collateral mint
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Amount(pub u64);
+
+impl Amount {
+    pub fn checked_add(self, other: Amount) -> Result<Amount> {
+        Ok(Amount(self.0.checked_add(other.0).ok_or(ErrorCode::Overflow)?))
+    }
+
+    pub fn checked_sub(self, other: Amount) -> Result<Amount> {
+        Ok(Amount(self.0.checked_sub(other.0).ok_or(ErrorCode::Overflow)?))
+    }
+
+    /// Share of this amount backing `part` out of `whole` contracts, rounded down.
+    /// The last part gets whatever is left, so releasing every part releases exactly this amount
+    pub fn pro_rata(self, part: Quantity, whole: Quantity) -> Result<Amount> {
+        require!(part <= whole, ErrorCode::Overflow);
+        if part.0 == 0 {
+            return Ok(Amount(0));
+        }
+        if part == whole {
+            return Ok(self);
+        }
+        let value = (self.0 as u128) * (part.0 as u128) / (whole.0 as u128);
+        Ok(Amount(value as u64))
+    }
+}
+
+/// Fixed-point price of one contract: `raw / 10^decimals` collateral per contract,
+/// where `decimals` is the `vayoo_precisions` of the contract the price belongs to
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct Price {
+    raw: u64,
+}
+
+impl Price {
+    pub fn new(raw: u64) -> Self {
+        Price { raw }
+    }
+
+    pub fn raw(&self) -> u64 {
+        self.raw
+    }
+
+    pub fn checked_add(self, other: Price) -> Result<Price> {
+        Ok(Price::new(self.raw.checked_add(other.raw).ok_or(ErrorCode::Overflow)?))
+    }
+
+    pub fn checked_sub(self, other: Price) -> Result<Price> {
+        Ok(Price::new(self.raw.checked_sub(other.raw).ok_or(ErrorCode::Overflow)?))
+    }
+
+    /// Collateral worth `quantity` contracts at this price
+    pub fn collateral_for(
+        &self,
+        quantity: Quantity,
+        decimals: u8,
+        rounding: Rounding,
+    ) -> Result<Amount> {
+        let numerator = (quantity.0 as u128)
+            .checked_mul(self.raw as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        let denominator = 10u128.checked_pow(decimals as u32).ok_or(ErrorCode::Overflow)?;
+
+        let mut value = numerator / denominator;
+        if rounding == Rounding::Up && numerator % denominator != 0 {
+            value += 1;
+        }
+        let value = u64::try_from(value).map_err(|_| error!(ErrorCode::Overflow))?;
+        Ok(Amount(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collateral_for_rounds_as_asked() {
+        let price = Price::new(1_500_001);
+        let down = price.collateral_for(Quantity(3), 6, Rounding::Down).unwrap();
+        let up = price.collateral_for(Quantity(3), 6, Rounding::Up).unwrap();
+        assert_eq!(down, Amount(4));
+        assert_eq!(up, Amount(5));
+
+        let exact = Price::new(2_000_000);
+        assert_eq!(exact.collateral_for(Quantity(7), 6, Rounding::Up).unwrap(), Amount(14));
+    }
+
+    #[test]
+    fn amount_arithmetic_is_checked() {
+        assert_eq!(Amount(5).checked_sub(Amount(3)).unwrap(), Amount(2));
+        assert!(Amount(3).checked_sub(Amount(5)).is_err());
+        assert!(Amount(u64::MAX).checked_add(Amount(1)).is_err());
+        assert!(Quantity(0).checked_sub(Quantity(1)).is_err());
+    }
+
+    #[test]
+    fn collateral_for_rejects_overflow_and_unsupported_decimals() {
+        assert!(Price::new(u64::MAX).collateral_for(Quantity(2), 0, Rounding::Down).is_err());
+        assert!(Price::new(1).collateral_for(Quantity(2), u8::MAX, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn pro_rata_releases_the_whole_amount_over_all_parts() {
+        let locked = Amount(1_000_001);
+        let first = locked.pro_rata(Quantity(1), Quantity(3)).unwrap();
+        let rest = locked.checked_sub(first).unwrap();
+        let second = rest.pro_rata(Quantity(1), Quantity(2)).unwrap();
+        let last = rest.checked_sub(second).unwrap().pro_rata(Quantity(1), Quantity(1)).unwrap();
+        assert_eq!((first, second, last), (Amount(333_333), Amount(333_334), Amount(333_334)));
+        assert_eq!(locked.pro_rata(Quantity(0), Quantity(0)).unwrap(), Amount(0));
+        assert!(locked.pro_rata(Quantity(4), Quantity(3)).is_err());
+    }
 }
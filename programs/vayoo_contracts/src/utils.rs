@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::states::ContractState;
+use crate::states::{ContractState, GlobalState};
+use crate::types::{PausableInstruction, SettlementStage};
+
+// Asserts the protocol is not paused, or still allows the instruction while paused
+pub fn not_paused(global_state: &GlobalState, instruction: PausableInstruction) -> Result<()> {
+    if global_state.paused && global_state.pause_allowlist & instruction.bit() == 0 {
+        return err!(ErrorCode::ProtocolPaused);
+    }
+    Ok(())
+}
 
 // Asserts the Contract is still accepting Deposit's
 pub fn unrestricted_deposit_phase(contract_state: &ContractState) -> Result<()> {
@@ -36,6 +45,36 @@ pub fn unrestricted_trading_phase(contract_state: &ContractState) -> Result<()>
     Ok(())
 }
 
+// Asserts the Contract has not reached maturity yet
+pub fn before_maturity(contract_state: &ContractState) -> Result<()> {
+    if contract_state.is_settling {
+        return err!(ErrorCode::IsSettling);
+    }
+    let time_now: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    if time_now >= contract_state.ending_time {
+        return err!(ErrorCode::ContractEnded);
+    }
+    Ok(())
+}
+
+// Asserts the Contract is not in emergency mode, which replaces settlement
+pub fn not_in_emergency(contract_state: &ContractState) -> Result<()> {
+    if contract_state.is_emergency {
+        return err!(ErrorCode::EmergencyMode);
+    }
+    Ok(())
+}
+
+// Asserts the Contract ending price is final and payouts can go through
+pub fn finalized_settlement(contract_state: &ContractState) -> Result<()> {
+    let time_now: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    match contract_state.settlement_stage(time_now) {
+        SettlementStage::Active => err!(ErrorCode::NotSettling),
+        SettlementStage::Settling => err!(ErrorCode::SettlementNotFinalized),
+        SettlementStage::Finalized => Ok(()),
+    }
+}
+
 // Asserts the Contract is still accepting Deposit's
 pub fn settling_mode(contract_state: &ContractState) -> Result<()> {
     if !contract_state.is_settling {
@@ -1,6 +1,8 @@
 export const GLOBAL_STATE_SEED = "GLOBAL_STATE_SEED";
-export const PYTH_FEED = "GVXRSBjFk6e6J3NbVPXohDJetcTjaeeuykUpbQF8UoMU"; // (BTC/USD) Pyth Feed on Mainnet
+export const ROLES_SEED = "ROLES_SEED";
+export const PYTH_FEED = process.env.PYTH_FEED ?? "GVXRSBjFk6e6J3NbVPXohDJetcTjaeeuykUpbQF8UoMU"; // (BTC/USD) Pyth Feed on Mainnet, or a mock feed on localnet
 export const SWITCHBOARD_FEED = "Hemj3drbpJPgUnvrXwqDFp5D49pNBMgQGKvbyD2Uu8g8"; // (RLB/USD) Switchboard Feed on Mainnet
 
 export const ONE_WEEK_IN_SECONDS = 1209600;
-export const USDC_DECIMALS = 6;
\ No newline at end of file
+export const USDC_DECIMALS = 6;
+export const PRICE_DECIMALS = 6; // decimals of every contract price and amplitude
\ No newline at end of file
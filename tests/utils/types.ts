@@ -1,5 +1,48 @@
 export const FeedType = {
     Pyth: 0,
-    Switchboard: 1
+    Switchboard: 1,
+    Aggregate: 2,
+    Manual: 3,
+    PythPull: 4,
+    SwitchboardOnDemand: 5,
+    SignedAttestation: 6,
+    Ratio: 7,
+    Basket: 8
 }
 
+export const HaltFlag = {
+    All: 0,
+    Deposit: 1,
+    Trading: 2
+}
+
+export const PausableInstruction = {
+    InitializeUser: 0,
+    DepositCollateral: 1,
+    WithdrawCollateral: 2,
+    MintLContractMm: 3,
+    BurnLContractMm: 4,
+    LongUser: 5,
+    CloseLongUser: 6,
+    ShortUser: 7,
+    CloseShortUser: 8,
+    UserSettleLong: 9,
+    MmSettleLong: 10,
+    TriggerSettleMode: 11,
+    RecordSettlementSample: 12,
+    FinalizeSettlementProposal: 13
+}
+
+export const Role = {
+    Pauser: 0,
+    Settler: 1,
+    ParameterAdmin: 2
+}
+
+export const RiskParameter = {
+    LimitingAmplitude: 0,
+    MaxConfidenceBps: 1,
+    MaxStalenessSettleSecs: 2,
+    DisputeWindowSecs: 3,
+    MaxStalenessInitSecs: 4
+}
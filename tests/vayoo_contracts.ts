@@ -32,10 +32,12 @@ import { sleep, toNativeAmount } from "./utils";
 import {
   GLOBAL_STATE_SEED,
   PYTH_FEED,
+  PRICE_DECIMALS,
+  ROLES_SEED,
   SWITCHBOARD_FEED,
   USDC_DECIMALS,
 } from "./utils/constants";
-import { FeedType } from "./utils/types";
+import { FeedType, PausableInstruction } from "./utils/types";
 import { addLiquidity, createWhirlpool } from "./whirlpoolUtils";
 import { ORCA_WHIRLPOOL_PROGRAM_ID } from "./whirlpoolUtils/utils/constants";
 import {
@@ -61,13 +63,17 @@ describe("vayoo_contracts", () => {
   const testUserWallet = new anchor.Wallet(testUser);
   const pythFeed = new PublicKey(PYTH_FEED);
   const switchboardFeed = new PublicKey(SWITCHBOARD_FEED);
+  const oracleConfig = {
+    maxConfidenceBps: new BN(100), // 1%
+    maxStalenessInitSecs: new BN(60),
+    maxStalenessSettleSecs: new BN(60),
+  };
   let usdcMint: PublicKey;
 
   let starting_price_global=0
 
   let accounts: any = {
-    pythFeed,
-    switchboardFeed,
+    oracle: switchboardFeed,
     systemProgram: SystemProgram.programId,
     rent: SYSVAR_RENT_PUBKEY,
     tokenProgram: TOKEN_PROGRAM_ID,
@@ -141,8 +147,29 @@ describe("vayoo_contracts", () => {
     assert.ok(globalStateAccount.totalTvlUsdc.toNumber() == 0);
   });
 
+  it("Create Roles", async () => {
+    const [rolesKey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(ROLES_SEED)],
+      program.programId
+    );
+    accounts.roles = rolesKey;
+
+    await program.methods
+      .initializeRoles()
+      .accounts({
+        ...accounts,
+        authority: superUser.publicKey,
+      })
+      .signers([superUser])
+      .rpc();
+
+    const rolesAccount = await program.account.roles.fetch(rolesKey);
+    assert.ok(rolesAccount.settler.equals(web3.PublicKey.default));
+  });
+
   it("Initialize Contract Account/State - Switchboard", async () => {
-    const amplitude = new BN(100_000);
+    // Band of 0.05 USD around the RLB price, with PRICE_DECIMALS
+    const amplitude = new BN(50_000);
     
     let need_to_find_relevant_mint = true;
     let contractName = "sb-xv1";
@@ -227,7 +254,8 @@ describe("vayoo_contracts", () => {
         contractStateKeyBump,
         contractEndTime,
         amplitude,
-        FeedType.Switchboard
+        FeedType.Switchboard,
+        oracleConfig
       )
       .accounts({
         ...accounts,
@@ -238,15 +266,23 @@ describe("vayoo_contracts", () => {
         console.log(e);
       });
 
+    // settle tests pay out right after maturity
+    await program.methods
+      .adminSetsDisputeWindow(new BN(0))
+      .accounts({ ...accounts })
+      .signers([superUser])
+      .rpc();
+
     const contractStateAccount = await program.account.contractState.fetch(
       contractStateKey
     );
+    assert.ok(contractStateAccount.disputeWindowSecs.eqn(0));
     if (DEBUG_MODE) {
       console.log("L Contract Mint Key: ", lcontractMint.toString());
       console.log("S Contract Mint Key: ", scontractMint.toString());
       console.log(
         "Contract Starting Price: ",
-        contractStateAccount.startingPrice.toString()
+        contractStateAccount.startingPrice.raw.toString()
       );
       console.log(
         "Contract Expo: ",
@@ -255,6 +291,15 @@ describe("vayoo_contracts", () => {
     }
     assert.ok(contractStateAccount.isHalted == false);
     assert.ok(contractStateAccount.oracleFeedKey.equals(switchboardFeed));
+
+    // The price and the amplitude share PRICE_DECIMALS, the band sits around the starting price
+    const startingPrice = contractStateAccount.startingPrice.raw;
+    const lowerBound = startingPrice.sub(amplitude.divn(2));
+    const upperBound = startingPrice.add(amplitude.divn(2));
+    assert.ok(contractStateAccount.vayooPrecisions == PRICE_DECIMALS);
+    assert.ok(contractStateAccount.limitingAmplitude.raw.eq(amplitude));
+    assert.ok(lowerBound.gtn(0));
+    assert.ok(upperBound.sub(lowerBound).eq(amplitude));
   });
 
   it("Cannot Trigger Settle Mode - Maturity Not Reached", async () => {
@@ -402,6 +447,30 @@ describe("vayoo_contracts", () => {
     );
   });
 
+  it("Pause blocks deposits", async () => {
+    const withdrawOnly = new BN(1 << PausableInstruction.WithdrawCollateral);
+    await program.methods
+      .adminSetsPaused(true, withdrawOnly)
+      .accounts({ ...accounts, authority: superUser.publicKey })
+      .signers([superUser])
+      .rpc();
+
+    let msg = "";
+    await program.methods
+      .depositCollateral(new BN(toNativeAmount(1, USDC_DECIMALS)))
+      .accounts({ ...accounts })
+      .signers([testUser])
+      .rpc()
+      .catch((e) => (msg = e.error.errorCode.code));
+    assert.ok(msg == "ProtocolPaused");
+
+    await program.methods
+      .adminSetsPaused(false, withdrawOnly)
+      .accounts({ ...accounts, authority: superUser.publicKey })
+      .signers([superUser])
+      .rpc();
+  });
+
   it("Mint lcontract as mm", async () => {
     const mmLcontractAta = await getOrCreateAssociatedTokenAccount(
       connection,
@@ -632,7 +701,8 @@ describe("vayoo_contracts", () => {
     const contractName = "v1";
     const timeNow = Math.floor(Date.now() / 1000);
     const contractEndTime = new BN(timeNow + 3); // 3 seconds into the future
-    const amplitude = new BN(30);
+    // Band of 2,000 USD around the BTC price
+    const amplitude = new BN(toNativeAmount(2_000, PRICE_DECIMALS));
 
     const [scontractMint, scontractMintBump] =
       anchor.web3.PublicKey.findProgramAddressSync(
@@ -670,10 +740,12 @@ describe("vayoo_contracts", () => {
         contractStateKeyBump,
         contractEndTime,
         amplitude,
-        FeedType.Pyth
+        FeedType.Pyth,
+        oracleConfig
       )
       .accounts({
         ...accounts,
+        oracle: pythFeed,
         contractState: contractStateKey,
         lcontractMint: lcontractMint,
         scontractMint: scontractMint,
@@ -682,6 +754,14 @@ describe("vayoo_contracts", () => {
       .signers([superUser])
       .rpc();
 
+    // The BTC price is normalized from the Pyth exponent to PRICE_DECIMALS, like the amplitude
+    const contractStateAccount = await program.account.contractState.fetch(
+      contractStateKey
+    );
+    assert.ok(contractStateAccount.vayooPrecisions == PRICE_DECIMALS);
+    assert.ok(contractStateAccount.limitingAmplitude.raw.eq(amplitude));
+    assert.ok(contractStateAccount.startingPrice.raw.gt(amplitude.divn(2)));
+
     // delay by 4 seconds
     await sleep(4);
 
@@ -750,7 +830,8 @@ describe("vayoo_contracts", () => {
     const contractName = "v2";
     const timeNow = Math.floor(Date.now() / 1000);
     const contractEndTime = new BN(timeNow + 4); // 4 seconds into the future
-    const amplitude = new BN(30);
+    // Band of 2,000 USD around the BTC price
+    const amplitude = new BN(toNativeAmount(2_000, PRICE_DECIMALS));
 
     const [scontractMint, scontractMintBump] =
       anchor.web3.PublicKey.findProgramAddressSync(
@@ -788,10 +869,12 @@ describe("vayoo_contracts", () => {
         contractStateKeyBump,
         contractEndTime,
         amplitude,
-        FeedType.Pyth
+        FeedType.Pyth,
+        oracleConfig
       )
       .accounts({
         ...accounts,
+        oracle: pythFeed,
         contractState: contractStateKey,
         lcontractMint,
         scontractMint,
@@ -1423,7 +1506,7 @@ describe("vayoo_contracts", () => {
     
 
     if (mode_to_test==1){
-      const end_price = Number(contractStateAccountBefore.startingPrice)-10_000_000;
+      const end_price = Number(contractStateAccountBefore.startingPrice.raw)-10_000_000;
       console.log("Triggered with a price of ",end_price)
       await program.methods
         .adminTriggersSettleMode(new BN(end_price))
@@ -1435,7 +1518,7 @@ describe("vayoo_contracts", () => {
 
     if (mode_to_test==2){
       
-      const end_price = Number(contractStateAccountBefore.startingPrice)+35_000_000;
+      const end_price = Number(contractStateAccountBefore.startingPrice.raw)+35_000_000;
       console.log("Triggered with a price of ",end_price)
       await program.methods
         .adminTriggersSettleMode(new BN(end_price))
@@ -1452,11 +1535,11 @@ describe("vayoo_contracts", () => {
       console.log("Time difference from end - start: ", endTime - timeNow);
       console.log(
         "Starting Price: ",
-        contractStateAccount.startingPrice.toString()
+        contractStateAccount.startingPrice.raw.toString()
       );
       console.log(
         "Ending Price: ",
-        contractStateAccount.endingPrice.toString()
+        contractStateAccount.endingPrice.raw.toString()
       );
     }
     assert.ok(contractStateAccount.isSettling);